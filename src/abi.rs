@@ -1,43 +1,37 @@
 use alloy::sol;
 
 sol! {
-    #[sol(rpc)]
-    interface SystemConfig {
-        function batchInbox() external view returns (address);
-        function disputeGameFactory() external view returns (address);
-        function guardian() external view returns (address);
-        function l1CrossDomainMessenger() external view returns (address);
-        function l1ERC721Bridge() external view returns (address);
-        function l1StandardBridge() external view returns (address);
-        function optimismMintableERC20Factory() external view returns (address);
-        function optimismPortal() external view returns (address);
-        function owner() external view returns (address);
-        function proxyAdmin() external view returns (address);
-        function proxyAdminOwner() external view returns (address);
+    /// EIP-1967 proxy, as deployed for the L1 contracts
+    interface Proxy {
+        event Upgraded(address indexed implementation);
+        event AdminChanged(address previousAdmin, address newAdmin);
+
+        function implementation() external view returns (address);
+        function admin() external view returns (address);
     }
 
-    #[sol(rpc)]
-    interface DisputeGameFactory {
-        function gameImpls(uint32 gameType) external view returns (address);
+    /// Admin of every L1 proxy, including the legacy ChugSplash and ResolvedDelegate types
+    interface ProxyAdmin {
+        function getProxyAdmin(address proxy) external view returns (address);
+        function getProxyImplementation(address proxy) external view returns (address);
     }
 
-    #[sol(rpc)]
-    interface FaultDisputeGame {
-        function anchorStateRegistry() external view returns (address);
-        function vm() external view returns (address);
-        function weth() external view returns (address);
+    interface Ownable {
+        event OwnershipTransferred(address indexed previousOwner, address indexed newOwner);
+
+        function owner() external view returns (address);
     }
 
-    #[sol(rpc)]
-    interface PermissionedDisputeGame {
-        function challenger() external view returns (address);
-        function proposer() external view returns (address);
-        function weth() external view returns (address);
+    interface DisputeGameFactory {
+        event ImplementationSet(address indexed impl, uint32 indexed gameType);
+
+        function gameImpls(uint32 gameType) external view returns (address);
     }
 
     #[sol(rpc)]
-    interface MIPS {
-        function oracle() external view returns (address);
+    interface GnosisSafe {
+        function getThreshold() external view returns (uint256);
+        function VERSION() external view returns (string memory);
     }
 
     #[sol(rpc)]
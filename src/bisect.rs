@@ -0,0 +1,239 @@
+use alloy::eips::BlockId;
+use alloy::primitives::Address;
+use alloy::providers::{Provider, ReqwestProvider};
+use alloy::rpc::types::BlockTransactionsKind;
+use anyhow::{Context, Result, anyhow};
+use std::future::Future;
+
+use crate::abi::Multicall3;
+use crate::checks::{CheckConfig, Target};
+use crate::value::Value;
+
+/// The first block at which a check no longer matched the docs.
+#[derive(Debug)]
+pub struct Divergence {
+    pub block: u64,
+    pub timestamp: u64,
+    /// What the chain showed at `block`: the value read, or why none could be read
+    pub observed: String,
+}
+
+/// Re-runs a single check's call at arbitrary blocks to find when its value changed.
+pub struct Bisector<'a> {
+    pub provider: &'a ReqwestProvider,
+    pub multicall3: Address,
+    pub checks: &'a [CheckConfig],
+    pub check: &'a CheckConfig,
+    /// Documented address of the check's root row
+    pub root: Address,
+    pub expected: &'a Value,
+}
+
+impl Bisector<'_> {
+    /// Binary-searches `(good, bad]` for the first block where the check differs from the
+    /// docs. Returns `None` if it still matches at `bad`.
+    ///
+    /// Assumes the value changed once; if it flipped back and forth, some block where it
+    /// changed is reported, not necessarily the first.
+    pub async fn run(&self, good: u64, bad: u64) -> Result<Option<Divergence>> {
+        if good > bad {
+            return Err(anyhow!("Block {} is after block {}", good, bad));
+        }
+        if let Some(missing) = self.missing_code_at(good).await? {
+            return Err(anyhow!(
+                "{} has no code at block {}; pass --bisect-from a block after it was deployed",
+                missing,
+                good
+            ));
+        }
+        if let Some(observed) = self.differs_at(good).await? {
+            return Err(anyhow!(
+                "{} does not match the docs at block {} either (chain: {}); pass --bisect-from a block where it does",
+                self.check.name,
+                good,
+                observed
+            ));
+        }
+        let Some(observed) = self.differs_at(bad).await? else {
+            return Ok(None);
+        };
+
+        let (bad, observed) = first_block(good, bad, observed, |b| self.differs_at(b)).await?;
+
+        let block = self
+            .provider
+            .get_block(BlockId::number(bad), BlockTransactionsKind::Hashes)
+            .await
+            .context(format!("Failed to fetch block {}", bad))?
+            .ok_or_else(|| anyhow!("Block {} not found", bad))?;
+
+        Ok(Some(Divergence {
+            block: bad,
+            timestamp: block.header.timestamp,
+            observed,
+        }))
+    }
+
+    /// Describes the root or Multicall3 if it has no code at `block`.
+    async fn missing_code_at(&self, block: u64) -> Result<Option<String>> {
+        for (name, address) in [
+            (format!("Root contract {}", self.root), self.root),
+            (format!("Multicall3 ({})", self.multicall3), self.multicall3),
+        ] {
+            let code = self
+                .provider
+                .get_code_at(address)
+                .block_id(BlockId::number(block))
+                .await
+                .context(format!(
+                    "Failed to fetch code at block {} (bisecting needs an archive RPC)",
+                    block
+                ))?;
+            if code.is_empty() {
+                return Ok(Some(name));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Runs the check at `block`, resolving its target chain at the same block. Returns
+    /// what the chain showed if it differs from the docs.
+    async fn differs_at(&self, block: u64) -> Result<Option<String>> {
+        // Walk back to the root row, then resolve targets outwards from it
+        let mut path = vec![self.check];
+        while let Target::Check { check } = &path[path.len() - 1].target {
+            let dependency = self
+                .checks
+                .iter()
+                .find(|c| &c.name == check)
+                .ok_or_else(|| anyhow!("Unknown target check '{}'", check))?;
+            path.push(dependency);
+        }
+
+        let mut target = self.root;
+        for dependency in path[1..].iter().rev() {
+            match self.call_at(dependency, target, block).await? {
+                Ok(Value::Address(address)) => target = address,
+                Ok(other) => {
+                    return Ok(Some(format!(
+                        "target check {} returned {}",
+                        dependency.name, other
+                    )));
+                }
+                Err(e) => return Ok(Some(format!("target check {}: {}", dependency.name, e))),
+            }
+        }
+
+        match self.call_at(self.check, target, block).await? {
+            Ok(value) if &value == self.expected => Ok(None),
+            Ok(value) => Ok(Some(value.to_string())),
+            Err(e) => Ok(Some(e)),
+        }
+    }
+
+    /// Calls `check` on `target` at `block`, describing why if no value could be read.
+    async fn call_at(
+        &self,
+        check: &CheckConfig,
+        target: Address,
+        block: u64,
+    ) -> Result<Result<Value, String>> {
+        let multicall = Multicall3::new(self.multicall3, self.provider);
+        let call = Multicall3::Call3 {
+            target,
+            allowFailure: true,
+            callData: check.call_data.clone().into(),
+        };
+        let result = multicall
+            .aggregate3(vec![call])
+            .block(BlockId::number(block))
+            .call()
+            .await
+            .context(format!("Multicall at block {} failed", block))?
+            .returnData
+            .remove(0);
+
+        if !result.success {
+            return Ok(Err("view call failed".to_string()));
+        }
+        Ok(check
+            .decode(&result.returnData)
+            .map_err(|e| format!("error decoding return data: {}", e)))
+    }
+}
+
+/// Binary-searches `(low, high]` for the first block at which `probe` returns a value,
+/// given that it returns none at `low` and `value` at `high`. Returns that block along
+/// with the value `probe` returned there.
+async fn first_block<T, F, Fut>(
+    mut low: u64,
+    mut high: u64,
+    mut value: T,
+    mut probe: F,
+) -> Result<(u64, T)>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<Option<T>>>,
+{
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        match probe(mid).await? {
+            Some(v) => {
+                high = mid;
+                value = v;
+            }
+            None => low = mid,
+        }
+    }
+    Ok((high, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use std::cell::RefCell;
+
+    /// Probe reporting the block number from `first` on, recording every block it is asked.
+    fn probe_from(
+        first: u64,
+        asked: &RefCell<Vec<u64>>,
+    ) -> impl FnMut(u64) -> std::future::Ready<Result<Option<u64>>> + '_ {
+        move |block| {
+            asked.borrow_mut().push(block);
+            std::future::ready(Ok((block >= first).then_some(block)))
+        }
+    }
+
+    #[test]
+    fn finds_first_differing_block() {
+        let asked = RefCell::new(Vec::new());
+        for first in [1, 2, 637, 999, 1000] {
+            let found = block_on(first_block(0, 1000, 1000, probe_from(first, &asked))).unwrap();
+            assert_eq!(found, (first, first));
+        }
+    }
+
+    #[test]
+    fn adjacent_bounds_need_no_probe() {
+        let asked = RefCell::new(Vec::new());
+        let found = block_on(first_block(41, 42, 42, probe_from(42, &asked))).unwrap();
+        assert_eq!(found, (42, 42));
+        assert!(asked.borrow().is_empty());
+    }
+
+    #[test]
+    fn probes_logarithmically_many_blocks() {
+        let asked = RefCell::new(Vec::new());
+        block_on(first_block(0, 1 << 20, 1 << 20, probe_from(12_345, &asked))).unwrap();
+        assert_eq!(asked.borrow().len(), 20);
+    }
+
+    #[test]
+    fn propagates_probe_errors() {
+        let result = block_on(first_block(0, 8, (), |_| async {
+            Err::<Option<()>, _>(anyhow!("RPC down"))
+        }));
+        assert!(result.is_err());
+    }
+}
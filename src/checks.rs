@@ -0,0 +1,691 @@
+use alloy::dyn_abi::{DynSolType, DynSolValue, FunctionExt, JsonAbiExt};
+use alloy::json_abi::Function;
+use alloy::primitives::{Address, B256, U256};
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::constants::L1_TO_L2_ALIAS_OFFSET;
+use crate::derived::Derivation;
+use crate::registry::Layer;
+use crate::value::Value;
+
+/// Check definitions shipped with the binary, used when no `--checks` file is given.
+pub const DEFAULT_CHECKS: &str = include_str!("../checks/default.toml");
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CheckFile {
+    #[serde(rename = "check", default)]
+    checks: Vec<CheckDef>,
+}
+
+/// A single `[[check]]` entry as written in the check file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CheckDef {
+    name: String,
+    #[serde(default)]
+    layer: Layer,
+    doc_row: Option<String>,
+    #[serde(default)]
+    doc_network: DocNetwork,
+    expected: Option<String>,
+    expected_check: Option<String>,
+    expected_derived: Option<Derivation>,
+    target: Target,
+    function: String,
+    #[serde(default)]
+    args: Vec<String>,
+    returns: String,
+    convert: Option<Conversion>,
+    expected_convert: Option<ExpectedConversion>,
+}
+
+/// Which docs table of the network pair a row is looked up in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocNetwork {
+    /// The L1 contracts table of the L1 network
+    #[default]
+    L1Contracts,
+    /// The L2 contracts table of the L2 network, e.g. for the L2 side of a bridge pair
+    L2Contracts,
+    /// The admin roles table of the L2 network (the roles themselves live on L1)
+    AdminRoles,
+}
+
+/// Contract a check calls.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum Target {
+    /// Address documented in this row of the contracts table of the check's layer
+    DocRow { doc_row: String },
+    /// Address returned on-chain by an earlier check
+    Check { check: String },
+}
+
+/// Post-processing applied to a decoded return value before comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Conversion {
+    /// Read a bytes32 as a left-padded address, rejecting non-zero padding
+    WordToAddress,
+}
+
+/// Transformation applied to the documented value before comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpectedConversion {
+    /// The L2 alias of an L1 address, which L1 contracts act as on L2
+    L1ToL2Alias,
+}
+
+/// Where a check's expected value comes from.
+#[derive(Debug, Clone)]
+pub enum Expected {
+    /// Address of the docs row with this name
+    DocRow(String, DocNetwork),
+    /// Fixed value known ahead of time, e.g. a game type
+    Literal(Value),
+    /// On-chain result of an earlier check, e.g. the VM of another game type
+    Check(String),
+    /// Address derived from the network's registry entry, e.g. the Batch Inbox
+    Derived(Derivation),
+}
+
+/// A validated check, ready to be encoded into a multicall.
+#[derive(Debug)]
+pub struct CheckConfig {
+    pub name: String,
+    /// Chain the call is made on
+    pub layer: Layer,
+    pub expected: Expected,
+    pub target: Target,
+    pub call_data: Vec<u8>,
+    function: Function,
+    convert: Option<Conversion>,
+    expected_convert: Option<ExpectedConversion>,
+}
+
+impl CheckConfig {
+    /// Docs row at the start of this check's target chain: the one address the check
+    /// depends on the docs for.
+    pub fn root<'a>(&'a self, checks: &'a [CheckConfig]) -> Result<&'a str> {
+        let mut current = self;
+        for _ in 0..=checks.len() {
+            match &current.target {
+                Target::DocRow { doc_row } => return Ok(doc_row),
+                Target::Check { check } => {
+                    current = checks
+                        .iter()
+                        .find(|c| &c.name == check)
+                        .ok_or_else(|| anyhow!("Unknown target check '{}'", check))?;
+                }
+            }
+        }
+        Err(anyhow!("Check '{}' depends on itself", self.name))
+    }
+
+    /// Applies `expected_convert` to the documented value, describing the conversion so the
+    /// documented value can be shown alongside the converted one.
+    pub fn convert_expected(&self, documented: Value) -> Result<(Value, Option<String>)> {
+        match (self.expected_convert, documented) {
+            (None, value) => Ok((value, None)),
+            (Some(ExpectedConversion::L1ToL2Alias), Value::Address(l1)) => {
+                let aliased = l1_to_l2_alias(l1);
+                Ok((
+                    Value::Address(aliased),
+                    Some(format!("L2 alias of documented L1 address {}", l1)),
+                ))
+            }
+            (Some(conversion), other) => Err(anyhow!("Cannot apply {:?} to {}", conversion, other)),
+        }
+    }
+
+    /// Decodes the raw return data of the call into a comparable value.
+    pub fn decode(&self, data: &[u8]) -> Result<Value> {
+        let output = self.function.abi_decode_output(data, true)?;
+        let value = output
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("{} returned no values", self.function.name))?;
+
+        match self.convert {
+            None => to_value(value),
+            Some(Conversion::WordToAddress) => match value {
+                DynSolValue::FixedBytes(word, 32) => word_to_address(word).map(Value::Address),
+                other => Err(anyhow!("Cannot convert {:?} to an address", other)),
+            },
+        }
+    }
+}
+
+/// Parses and validates a check file, encoding each call up front so that mistakes in
+/// signatures or arguments surface before any RPC traffic.
+pub fn load_checks(source: &str) -> Result<Vec<CheckConfig>> {
+    let file: CheckFile = toml::from_str(source).context("Invalid check file")?;
+
+    let mut checks: Vec<CheckConfig> = Vec::with_capacity(file.checks.len());
+    for def in file.checks {
+        let name = def.name.clone();
+        let check =
+            compile_check(def, &checks).with_context(|| format!("Invalid check '{}'", name))?;
+        checks.push(check);
+    }
+
+    Ok(checks)
+}
+
+/// Rewrites every docs-row target other than `root` to the check that verifies that row,
+/// so only `root` is taken from the docs and every other call target is read on-chain.
+///
+/// A wrong docs value then only fails its own check, instead of silently pointing later
+/// checks at the wrong contract. L2 checks are left alone, since their targets are
+/// predeploys at fixed addresses.
+///
+/// When several checks verify the same row, the one with the fewest calls between it and
+/// `root` derives it. Equally close producers, such as the FDG and PDG reads of the
+/// AnchorStateRegistry, each verify the row against the docs, so the first one is used.
+pub fn root_on_chain(checks: &mut [CheckConfig], root: &str) -> Result<()> {
+    let producers = closest_producers(checks, root);
+
+    for check in checks.iter_mut().filter(|c| c.layer == Layer::L1) {
+        let Target::DocRow { doc_row } = &check.target else {
+            continue;
+        };
+        if doc_row.eq_ignore_ascii_case(root) {
+            continue;
+        }
+
+        let producer = producers
+            .get(&doc_row.to_ascii_lowercase())
+            .ok_or_else(|| {
+                anyhow!(
+                    "No check derives {} on-chain from {}, so '{}' cannot be rooted there",
+                    doc_row,
+                    root,
+                    check.name
+                )
+            })?;
+        check.target = Target::Check {
+            check: producer.clone(),
+        };
+    }
+
+    for check in checks.iter() {
+        check.root(checks)?;
+    }
+
+    Ok(())
+}
+
+/// For each L1 contracts row, the first L1 check verifying it with the fewest calls from
+/// `root`, keyed by the lowercased row. Rows no check reaches from `root` are absent.
+fn closest_producers(checks: &[CheckConfig], root: &str) -> HashMap<String, String> {
+    // Calls needed to read a row on-chain starting from the root, refined until stable
+    let mut row_distance: HashMap<String, usize> = HashMap::new();
+    row_distance.insert(root.to_ascii_lowercase(), 0);
+
+    let distance = |check: &CheckConfig, rows: &HashMap<String, usize>| {
+        let mut calls = 1;
+        let mut current = check;
+        // Target checks are defined earlier, so this chain always ends at a docs row
+        loop {
+            match &current.target {
+                Target::DocRow { doc_row } => {
+                    return rows.get(&doc_row.to_ascii_lowercase()).map(|d| d + calls);
+                }
+                Target::Check { check } => {
+                    current = checks.iter().find(|c| &c.name == check)?;
+                    calls += 1;
+                }
+            }
+        }
+    };
+    let produced_row = |check: &CheckConfig| match &check.expected {
+        Expected::DocRow(row, DocNetwork::L1Contracts) if check.layer == Layer::L1 => {
+            Some(row.to_ascii_lowercase())
+        }
+        _ => None,
+    };
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for check in checks {
+            let (Some(row), Some(d)) = (produced_row(check), distance(check, &row_distance)) else {
+                continue;
+            };
+            if row_distance.get(&row).is_none_or(|&current| d < current) {
+                row_distance.insert(row, d);
+                changed = true;
+            }
+        }
+    }
+
+    let mut producers = HashMap::new();
+    for check in checks {
+        if let Some(row) = produced_row(check)
+            && let Some(d) = distance(check, &row_distance)
+            && row_distance.get(&row) == Some(&d)
+        {
+            producers.entry(row).or_insert_with(|| check.name.clone());
+        }
+    }
+    producers
+}
+
+fn compile_check(def: CheckDef, earlier: &[CheckConfig]) -> Result<CheckConfig> {
+    if earlier.iter().any(|c| c.name == def.name) {
+        return Err(anyhow!("Duplicate check name"));
+    }
+
+    if let Target::Check { check } = &def.target {
+        match earlier.iter().find(|c| &c.name == check) {
+            None => return Err(anyhow!("Target check '{}' must be defined earlier", check)),
+            Some(c) if c.layer != def.layer => {
+                return Err(anyhow!("Target check '{}' runs on another layer", check));
+            }
+            Some(_) => {}
+        }
+    }
+
+    let function = Function::parse(&format!(
+        "function {} returns ({})",
+        def.function, def.returns
+    ))
+    .map_err(|e| anyhow!("Invalid function signature '{}': {}", def.function, e))?;
+    if function.outputs.len() != 1 {
+        return Err(anyhow!("Exactly one return type is supported"));
+    }
+
+    if function.inputs.len() != def.args.len() {
+        return Err(anyhow!(
+            "{} expects {} argument(s), {} given",
+            def.function,
+            function.inputs.len(),
+            def.args.len()
+        ));
+    }
+    let args = function
+        .inputs
+        .iter()
+        .zip(&def.args)
+        .map(|(param, arg)| {
+            let ty = DynSolType::parse(&param.ty)?;
+            ty.coerce_str(arg)
+                .map_err(|e| anyhow!("Invalid argument '{}' for {}: {}", arg, param.ty, e))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let call_data = function.abi_encode_input(&args)?;
+
+    // The value compared against the expectation, after any conversion
+    let value_type = match def.convert {
+        None => DynSolType::parse(&function.outputs[0].ty)?,
+        Some(Conversion::WordToAddress) => {
+            if function.outputs[0].ty != "bytes32" {
+                return Err(anyhow!("word_to_address requires a bytes32 return type"));
+            }
+            DynSolType::Address
+        }
+    };
+    // Mirrors `to_value`, so an unsupported type fails here rather than on every run
+    match value_type {
+        DynSolType::Address
+        | DynSolType::FixedBytes(32)
+        | DynSolType::Uint(_)
+        | DynSolType::String => {}
+        ref other => return Err(anyhow!("Unsupported return type {}", other)),
+    }
+
+    if def.expected_convert.is_some()
+        && (def.doc_row.is_none() || value_type != DynSolType::Address)
+    {
+        return Err(anyhow!("expected_convert requires a doc_row address check"));
+    }
+
+    let expected = match (
+        def.doc_row,
+        def.expected,
+        def.expected_check,
+        def.expected_derived,
+    ) {
+        (Some(row), None, None, None) => {
+            if value_type != DynSolType::Address {
+                return Err(anyhow!("doc_row checks must produce an address"));
+            }
+            Expected::DocRow(row, def.doc_network)
+        }
+        (None, Some(literal), None, None) => {
+            let value = value_type
+                .coerce_str(&literal)
+                .map_err(|e| anyhow!("Invalid expected value '{}': {}", literal, e))?;
+            Expected::Literal(to_value(value)?)
+        }
+        (None, None, Some(check), None) => {
+            match earlier.iter().find(|c| c.name == check) {
+                None => {
+                    return Err(anyhow!(
+                        "Expected check '{}' must be defined earlier",
+                        check
+                    ));
+                }
+                Some(c) if c.layer != def.layer => {
+                    return Err(anyhow!("Expected check '{}' runs on another layer", check));
+                }
+                Some(_) => {}
+            }
+            Expected::Check(check)
+        }
+        (None, None, None, Some(derivation)) => {
+            if value_type != DynSolType::Address {
+                return Err(anyhow!("expected_derived checks must produce an address"));
+            }
+            Expected::Derived(derivation)
+        }
+        _ => {
+            return Err(anyhow!(
+                "Exactly one of doc_row, expected, expected_check or expected_derived must be set"
+            ));
+        }
+    };
+
+    Ok(CheckConfig {
+        name: def.name,
+        layer: def.layer,
+        expected,
+        target: def.target,
+        call_data,
+        function,
+        convert: def.convert,
+        expected_convert: def.expected_convert,
+    })
+}
+
+fn to_value(value: DynSolValue) -> Result<Value> {
+    match value {
+        DynSolValue::Address(a) => Ok(Value::Address(a)),
+        DynSolValue::FixedBytes(word, 32) => Ok(Value::Bytes32(word)),
+        DynSolValue::Uint(u, _) => Ok(Value::Uint(u)),
+        DynSolValue::String(s) => Ok(Value::String(s)),
+        other => Err(anyhow!("Unsupported return value {:?}", other)),
+    }
+}
+
+/// Applies the L1->L2 address alias: the L1 address plus the alias offset, modulo 2^160.
+fn l1_to_l2_alias(address: Address) -> Address {
+    let sum = U256::from_be_slice(address.as_slice())
+        .wrapping_add(U256::from_be_slice(L1_TO_L2_ALIAS_OFFSET.as_slice()));
+    Address::from_word(sum.into())
+}
+
+/// Extracts an address from a left-padded word, e.g. `SystemConfig.batcherHash`, which
+/// holds a version byte (currently 0) followed by the batcher address.
+fn word_to_address(word: B256) -> Result<Address> {
+    if word[..12].iter().any(|b| *b != 0) {
+        return Err(anyhow!("Unsupported word format for an address: {}", word));
+    }
+    Ok(Address::from_word(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, b256};
+
+    #[test]
+    fn aliases_l1_address_by_adding_the_offset() {
+        assert_eq!(
+            l1_to_l2_alias(address!("7bB41C3008B3f03FE483B28b8DB90e19Cf07595c")),
+            address!("8cC51c3008b3f03Fe483B28B8Db90e19cF076a6d")
+        );
+        assert_eq!(l1_to_l2_alias(Address::ZERO), L1_TO_L2_ALIAS_OFFSET);
+    }
+
+    #[test]
+    fn alias_wraps_around_160_bits() {
+        assert_eq!(
+            l1_to_l2_alias(address!("ffffffffffffffffffffffffffffffffffffffff")),
+            address!("1111000000000000000000000000000000001110")
+        );
+    }
+
+    #[test]
+    fn reads_batcher_hash_as_a_left_padded_address() {
+        assert_eq!(
+            word_to_address(b256!(
+                "0000000000000000000000006cdebe940bc0f26850285caca097c11c33103e47"
+            ))
+            .unwrap(),
+            address!("6CDEbe940BC0F26850285cacA097C11c33103E47")
+        );
+        assert!(
+            word_to_address(b256!(
+                "0100000000000000000000006cdebe940bc0f26850285caca097c11c33103e47"
+            ))
+            .is_err()
+        );
+    }
+
+    /// Loads `checks` after a check rooted at SystemConfig that the others can refer to.
+    fn load(checks: &str) -> Result<Vec<CheckConfig>> {
+        load_checks(&format!(
+            r#"
+            [[check]]
+            name = "Portal"
+            doc_row = "OptimismPortal"
+            target = {{ doc_row = "SystemConfig" }}
+            function = "optimismPortal()"
+            returns = "address"
+            {}
+            "#,
+            checks
+        ))
+    }
+
+    fn error(checks: &str) -> String {
+        format!("{:#}", load(checks).unwrap_err())
+    }
+
+    #[test]
+    fn default_checks_load() {
+        let checks = load_checks(DEFAULT_CHECKS).unwrap();
+        assert!(!checks.is_empty());
+        for check in &checks {
+            check.root(&checks).unwrap();
+        }
+    }
+
+    /// The row or check `name` targets after rooting the default checks at SystemConfig.
+    fn rooted_target(checks: &[CheckConfig], name: &str) -> String {
+        let check = checks.iter().find(|c| c.name == name).unwrap();
+        match &check.target {
+            Target::DocRow { doc_row } => format!("row {}", doc_row),
+            Target::Check { check } => format!("check {}", check),
+        }
+    }
+
+    #[test]
+    fn roots_default_checks_at_the_closest_producer() {
+        let mut checks = load_checks(DEFAULT_CHECKS).unwrap();
+        root_on_chain(&mut checks, "SystemConfig").unwrap();
+
+        for (name, target) in [
+            ("OptimismPortal", "row SystemConfig"),
+            ("Fault Dispute Game", "check DisputeGameFactory"),
+            ("Permissioned Dispute Game", "check DisputeGameFactory"),
+            ("FaultDisputeGame Game Type", "check Fault Dispute Game"),
+            ("Challenger", "check Permissioned Dispute Game"),
+            (
+                "L1CrossDomainMessenger.portal() -> OptimismPortal",
+                "check L1CrossDomainMessenger",
+            ),
+            (
+                "L2StandardBridge (paired with L1StandardBridge)",
+                "check L1StandardBridge",
+            ),
+            (
+                "AnchorStateRegistryProxy.disputeGameFactory() -> DisputeGameFactoryProxy",
+                "check AnchorStateRegistry (FDG)",
+            ),
+            (
+                "DelayedWETHProxy (PDG).systemConfig() -> SystemConfig",
+                "check DelayedWETHProxy (PDG)",
+            ),
+        ] {
+            assert_eq!(rooted_target(&checks, name), target, "{}", name);
+        }
+        for check in &checks {
+            if check.layer == Layer::L1 {
+                assert_eq!(
+                    check.root(&checks).unwrap(),
+                    "SystemConfig",
+                    "{}",
+                    check.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_rows_derived_only_in_a_cycle() {
+        let mut checks = load(
+            r#"
+            [[check]]
+            name = "Bridge"
+            doc_row = "L1StandardBridge"
+            target = { doc_row = "L1CrossDomainMessenger" }
+            function = "bridge()"
+            returns = "address"
+
+            [[check]]
+            name = "Messenger"
+            doc_row = "L1CrossDomainMessenger"
+            target = { doc_row = "L1StandardBridge" }
+            function = "messenger()"
+            returns = "address"
+            "#,
+        )
+        .unwrap();
+        let err = root_on_chain(&mut checks, "SystemConfig").unwrap_err();
+        assert!(err.to_string().contains("No check derives"), "{}", err);
+    }
+
+    #[test]
+    fn encodes_arguments() {
+        let checks = load(
+            r#"
+            [[check]]
+            name = "Game"
+            expected = "0x0000000000000000000000000000000000000001"
+            target = { check = "Portal" }
+            function = "gameImpls(uint32)"
+            args = ["1"]
+            returns = "address"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(checks[1].call_data[..4], [0x1b, 0x68, 0x5b, 0x9e]);
+        assert_eq!(checks[1].call_data[4..], B256::with_last_byte(1)[..]);
+    }
+
+    #[test]
+    fn rejects_wrong_argument_count() {
+        let err = error(
+            r#"
+            [[check]]
+            name = "Game"
+            doc_row = "FaultDisputeGame"
+            target = { check = "Portal" }
+            function = "gameImpls(uint32)"
+            returns = "address"
+            "#,
+        );
+        assert!(err.contains("expects 1 argument(s), 0 given"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_mistyped_arguments() {
+        let err = error(
+            r#"
+            [[check]]
+            name = "Game"
+            doc_row = "FaultDisputeGame"
+            target = { check = "Portal" }
+            function = "gameImpls(uint32)"
+            args = ["zero"]
+            returns = "address"
+            "#,
+        );
+        assert!(err.contains("Invalid argument 'zero'"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_duplicate_names() {
+        let err = error(
+            r#"
+            [[check]]
+            name = "Portal"
+            expected = "1"
+            target = { doc_row = "SystemConfig" }
+            function = "version()"
+            returns = "uint256"
+            "#,
+        );
+        assert!(err.contains("Duplicate check name"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_forward_references() {
+        let target = error(
+            r#"
+            [[check]]
+            name = "Early"
+            doc_row = "SystemConfig"
+            target = { check = "Later" }
+            function = "systemConfig()"
+            returns = "address"
+            "#,
+        );
+        assert!(
+            target.contains("Target check 'Later' must be defined earlier"),
+            "{}",
+            target
+        );
+
+        let expected = error(
+            r#"
+            [[check]]
+            name = "Early"
+            expected_check = "Later"
+            target = { doc_row = "SystemConfig" }
+            function = "systemConfig()"
+            returns = "address"
+            "#,
+        );
+        assert!(
+            expected.contains("Expected check 'Later' must be defined earlier"),
+            "{}",
+            expected
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_return_types() {
+        for expectation in [r#"expected = "true""#, r#"expected_check = "Portal""#] {
+            let err = error(&format!(
+                r#"
+                [[check]]
+                name = "Paused"
+                {}
+                target = {{ check = "Portal" }}
+                function = "paused()"
+                returns = "bool"
+                "#,
+                expectation
+            ));
+            assert!(err.contains("Unsupported return type bool"), "{}", err);
+        }
+    }
+}
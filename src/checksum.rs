@@ -0,0 +1,173 @@
+use alloy::primitives::Address;
+use clap::ValueEnum;
+use std::str::FromStr;
+
+use crate::parser::Network;
+
+/// How strictly addresses without any checksum casing are treated.
+///
+/// Mixed-case addresses with an invalid EIP-55 checksum are always errors, since the
+/// casing points to a typo in the hex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ChecksumStrictness {
+    /// Accept all-lowercase and all-uppercase addresses
+    Lenient,
+    /// Report all-lowercase and all-uppercase addresses as warnings
+    Warn,
+    /// Report all-lowercase and all-uppercase addresses as errors
+    Strict,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug)]
+pub struct ChecksumIssue {
+    pub name: String,
+    pub line: usize,
+    pub address: String,
+    pub checksummed: String,
+    pub severity: Severity,
+    /// Whether the address carries no casing at all, so rewriting it cannot hide a typo
+    pub uncased: bool,
+}
+
+/// Lints every documented address against its EIP-55 checksum.
+pub fn lint_checksums(networks: &[Network], strictness: ChecksumStrictness) -> Vec<ChecksumIssue> {
+    let mut issues = Vec::new();
+
+    for contract in networks.iter().flat_map(|n| &n.contracts) {
+        let Ok(address) = Address::from_str(&contract.address) else {
+            continue;
+        };
+        let checksummed = address.to_checksum(None);
+        if contract.address == checksummed {
+            continue;
+        }
+
+        let hex = &contract.address[2..];
+        let uncased = hex == hex.to_ascii_lowercase() || hex == hex.to_ascii_uppercase();
+        let severity = match (uncased, strictness) {
+            (false, _) | (true, ChecksumStrictness::Strict) => Severity::Error,
+            (true, ChecksumStrictness::Warn) => Severity::Warning,
+            (true, ChecksumStrictness::Lenient) => continue,
+        };
+
+        issues.push(ChecksumIssue {
+            name: contract.name.clone(),
+            line: contract.line,
+            address: contract.address.clone(),
+            checksummed,
+            severity,
+            uncased,
+        });
+    }
+
+    issues
+}
+
+/// Rewrites the addresses of uncased issues to their checksummed form, in both the
+/// label and the link of the affected lines. Returns the new content and the number
+/// of addresses rewritten.
+///
+/// Addresses with an invalid mixed-case checksum are left alone so the likely typo
+/// can be reviewed by hand.
+pub fn fix_checksums(content: &str, issues: &[ChecksumIssue]) -> (String, usize) {
+    let mut fixed = 0;
+    let lines = content
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(i, line)| {
+            let mut line = line.to_string();
+            for issue in issues.iter().filter(|x| x.uncased && x.line == i + 1) {
+                line = replace_ignore_ascii_case(&line, &issue.address, &issue.checksummed);
+                fixed += 1;
+            }
+            line
+        })
+        .collect();
+
+    (lines, fixed)
+}
+
+fn replace_ignore_ascii_case(haystack: &str, needle: &str, replacement: &str) -> String {
+    let lower_haystack = haystack.to_ascii_lowercase();
+    let lower_needle = needle.to_ascii_lowercase();
+
+    let mut out = String::with_capacity(haystack.len());
+    let mut last = 0;
+    for (start, _) in lower_haystack.match_indices(&lower_needle) {
+        out.push_str(&haystack[last..start]);
+        out.push_str(replacement);
+        last = start + needle.len();
+    }
+    out.push_str(&haystack[last..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_networks;
+
+    const LOWER: &str = "0x2453c1216e49704d84ea98a4dacd95738f2fc8ec";
+    const CHECKSUMMED: &str = "0x2453c1216E49704d84eA98a4daCd95738F2fC8Ec";
+    // CHECKSUMMED with the casing of one letter flipped
+    const BAD_CASING: &str = "0x2453C1216E49704d84eA98a4daCd95738F2fC8Ec";
+
+    fn doc(address: &str) -> String {
+        format!(
+            "### Net\n\n| Name | Address |\n| - | - |\n| A | [{}](https://etherscan.io/address/{}) |\n",
+            address, address
+        )
+    }
+
+    fn lint(address: &str, strictness: ChecksumStrictness) -> Vec<ChecksumIssue> {
+        lint_checksums(&parse_networks(&doc(address)).unwrap(), strictness)
+    }
+
+    #[test]
+    fn grades_uncased_addresses_by_strictness() {
+        assert!(lint(CHECKSUMMED, ChecksumStrictness::Strict).is_empty());
+        assert!(lint(LOWER, ChecksumStrictness::Lenient).is_empty());
+        assert_eq!(
+            lint(LOWER, ChecksumStrictness::Warn)[0].severity,
+            Severity::Warning
+        );
+        assert_eq!(
+            lint(LOWER, ChecksumStrictness::Strict)[0].severity,
+            Severity::Error
+        );
+    }
+
+    #[test]
+    fn bad_mixed_casing_is_always_an_error() {
+        let issues = lint(BAD_CASING, ChecksumStrictness::Lenient);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert!(!issues[0].uncased);
+    }
+
+    #[test]
+    fn fixes_label_and_link_of_uncased_addresses() {
+        let content = doc(LOWER);
+        let issues = lint(LOWER, ChecksumStrictness::Warn);
+        let (fixed, count) = fix_checksums(&content, &issues);
+
+        assert_eq!(count, 1);
+        assert_eq!(fixed, doc(CHECKSUMMED));
+    }
+
+    #[test]
+    fn leaves_bad_mixed_casing_alone() {
+        let content = doc(BAD_CASING);
+        let issues = lint(BAD_CASING, ChecksumStrictness::Strict);
+        let (fixed, count) = fix_checksums(&content, &issues);
+
+        assert_eq!(count, 0);
+        assert_eq!(fixed, content);
+    }
+}
@@ -1,9 +1,62 @@
+use alloy::primitives::{Address, B256, address, b256};
+
 pub const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
-pub const MAINNET_RPC_URL_ENV: &str = "MAINNET_RPC_URL";
-pub const SEPOLIA_RPC_URL_ENV: &str = "SEPOLIA_RPC_URL";
-
-// Network Display Names
-pub const ETHEREUM_MAINNET: &str = "Ethereum Mainnet";
-pub const BASE_MAINNET: &str = "Base Mainnet";
-pub const ETHEREUM_SEPOLIA: &str = "Ethereum Testnet (Sepolia)";
-pub const BASE_SEPOLIA: &str = "Base Testnet (Sepolia)";
+
+// EIP-1967 storage slots: keccak256("eip1967.proxy.implementation") - 1 and
+// keccak256("eip1967.proxy.admin") - 1
+pub const EIP1967_IMPLEMENTATION_SLOT: B256 =
+    b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+pub const EIP1967_ADMIN_SLOT: B256 =
+    b256!("b53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103");
+
+// Document Section Names
+pub const L2_CONTRACTS_SECTION: &str = "L2 Contract Addresses";
+pub const L1_CONTRACTS_SECTION: &str = "L1 Contract Addresses";
+pub const ADMIN_SECTION: &str = "Base Admin Addresses";
+
+// Docs row trusted as the single root in chain-rooted mode
+pub const CHAIN_ROOT_ROW: &str = "SystemConfig";
+
+// Docs row of the address batches are posted to, derived from the L2 chain id
+pub const BATCH_INBOX_ROW: &str = "Batch Inbox";
+
+// Docs row holding the admin of every L1 proxy
+pub const PROXY_ADMIN_ROW: &str = "ProxyAdmin";
+
+// Admin roles row holding the owner of the L1 ProxyAdmin
+pub const PROXY_ADMIN_OWNER_ROW: &str = "Proxy Admin Owner (L1)";
+
+// L1 rows deployed behind a proxy managed by the ProxyAdmin, besides those whose name
+// ends in "Proxy"
+pub const PROXY_ROW_SUFFIX: &str = "Proxy";
+pub const PROXY_ROWS: &[&str] = &[
+    "L1CrossDomainMessenger",
+    "L1ERC721Bridge",
+    "L1StandardBridge",
+    "L2OutputOracle",
+    "OptimismMintableERC20Factory",
+    "OptimismPortal",
+    "SystemConfig",
+];
+
+// Proxies without EIP-1967 slots: the ResolvedDelegateProxy of L1CrossDomainMessenger
+// resolves its implementation through the AddressManager
+pub const NON_EIP1967_PROXY_ROWS: &[&str] = &["L1CrossDomainMessenger"];
+
+// Document Column Headers
+pub const KEY_TYPE_COLUMN: &str = "Type of Key";
+pub const IMPLEMENTATION_COLUMN: &str = "Implementation";
+
+// Implementations of L2 predeploy proxies live at this prefix followed by the last two
+// bytes of the predeploy address
+pub const PREDEPLOY_IMPLEMENTATION_NAMESPACE: Address =
+    address!("c0d3c0d3c0d3c0d3c0d3c0d3c0d3c0d3c0d30000");
+
+// Added to an L1 address to get the alias its L1->L2 messages are sent from on L2
+pub const L1_TO_L2_ALIAS_OFFSET: Address = address!("1111000000000000000000000000000000001111");
+
+// Largest block range requested in one eth_getLogs call, a common RPC provider limit
+pub const LOG_CHUNK_BLOCKS: u64 = 10_000;
+
+// Exit Codes: 1 for failed checks, 2 is taken by clap for usage errors
+pub const CONFIG_ERROR_EXIT_CODE: i32 = 3;
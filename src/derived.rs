@@ -0,0 +1,113 @@
+use alloy::primitives::Address;
+use serde::Deserialize;
+use std::str::FromStr;
+
+use crate::constants::{ADMIN_SECTION, BATCH_INBOX_ROW};
+use crate::parser::{Network, NetworkRef};
+use crate::registry::NetworkConfig;
+
+/// An address that follows from a network's registry entry rather than the docs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Derivation {
+    /// `0xff00…` followed by the decimal digits of the L2 chain id
+    BatchInbox,
+}
+
+impl Derivation {
+    pub fn derive(self, network: &NetworkConfig) -> Address {
+        match self {
+            Derivation::BatchInbox => batch_inbox(network.l2_chain_id),
+        }
+    }
+}
+
+/// Batch Inbox of an L2 by convention, e.g. `0xff00…8453` for chain id 8453.
+pub fn batch_inbox(l2_chain_id: u64) -> Address {
+    // A u64 has at most 20 decimal digits, well within the 38 left after the prefix
+    Address::from_str(&format!("ff{:0>38}", l2_chain_id)).expect("Invalid Batch Inbox address")
+}
+
+#[derive(Debug)]
+pub struct DerivationIssue {
+    pub name: String,
+    pub location: String,
+    pub line: usize,
+    pub documented: String,
+    pub derived: Address,
+    /// What the derived address follows from, e.g. "L2 chain id 8453"
+    pub source: String,
+}
+
+/// Compares documented rows against the addresses derivable from the registry, without
+/// any RPC access. Rows the docs do not list are skipped.
+pub fn check_derivations(networks: &[Network], registry: &[NetworkConfig]) -> Vec<DerivationIssue> {
+    let mut issues = Vec::new();
+
+    for config in registry {
+        let location = NetworkRef {
+            section: ADMIN_SECTION,
+            network: &config.l2_header,
+        };
+        let derived = Derivation::BatchInbox.derive(config);
+
+        for contract in networks
+            .iter()
+            .filter(|n| n.location() == location)
+            .flat_map(|n| &n.contracts)
+            .filter(|c| c.name.eq_ignore_ascii_case(BATCH_INBOX_ROW))
+        {
+            if Address::from_str(&contract.address).ok() == Some(derived) {
+                continue;
+            }
+            issues.push(DerivationIssue {
+                name: contract.name.clone(),
+                location: location.to_string(),
+                line: contract.line,
+                documented: contract.address.clone(),
+                derived,
+                source: format!("L2 chain id {}", config.l2_chain_id),
+            });
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_networks;
+    use crate::registry::{DEFAULT_NETWORKS, load_registry};
+    use alloy::primitives::address;
+
+    #[test]
+    fn batch_inbox_spells_the_chain_id_in_decimal() {
+        assert_eq!(
+            batch_inbox(8453),
+            address!("ff00000000000000000000000000000000008453")
+        );
+        assert_eq!(
+            batch_inbox(84532),
+            address!("ff00000000000000000000000000000000084532")
+        );
+        assert_eq!(
+            batch_inbox(u64::MAX),
+            address!("ff00000000000000000018446744073709551615")
+        );
+    }
+
+    #[test]
+    fn flags_documented_inbox_typos() {
+        let doc = "## Base Admin Addresses\n\n### Base Mainnet\n\n| Name | Address |\n| - | - |\n\
+                   | Batch Inbox | 0xff00000000000000000000000000000000008435 |\n\n\
+                   ### Base Testnet (Sepolia)\n\n| Name | Address |\n| - | - |\n\
+                   | Batch Inbox | 0xff00000000000000000000000000000000084532 |\n";
+        let networks = parse_networks(doc).unwrap();
+        let issues = check_derivations(&networks, &load_registry(DEFAULT_NETWORKS).unwrap());
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 7);
+        assert_eq!(issues[0].derived, batch_inbox(8453));
+    }
+}
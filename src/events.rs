@@ -0,0 +1,328 @@
+use alloy::eips::BlockId;
+use alloy::primitives::{Address, B256};
+use alloy::providers::{Provider, ReqwestProvider};
+use alloy::rpc::types::{BlockTransactionsKind, Filter, Log};
+use alloy::sol_types::{SolCall, SolEvent};
+use anyhow::{Context, Result, anyhow};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::abi::{DisputeGameFactory, Ownable, Proxy};
+use crate::constants::LOG_CHUNK_BLOCKS;
+
+/// An on-chain change to a value the docs may record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Upgraded {
+        implementation: Address,
+    },
+    AdminChanged {
+        previous: Address,
+        new: Address,
+    },
+    OwnershipTransferred {
+        previous: Address,
+        new: Address,
+    },
+    ImplementationSet {
+        implementation: Address,
+        game_type: u32,
+    },
+}
+
+impl Change {
+    fn decode(log: &Log) -> Result<Option<Change>> {
+        let change = match log.topic0() {
+            Some(&Proxy::Upgraded::SIGNATURE_HASH) => {
+                let event = log.log_decode::<Proxy::Upgraded>()?.inner.data;
+                Change::Upgraded {
+                    implementation: event.implementation,
+                }
+            }
+            Some(&Proxy::AdminChanged::SIGNATURE_HASH) => {
+                let event = log.log_decode::<Proxy::AdminChanged>()?.inner.data;
+                Change::AdminChanged {
+                    previous: event.previousAdmin,
+                    new: event.newAdmin,
+                }
+            }
+            Some(&Ownable::OwnershipTransferred::SIGNATURE_HASH) => {
+                let event = log
+                    .log_decode::<Ownable::OwnershipTransferred>()?
+                    .inner
+                    .data;
+                Change::OwnershipTransferred {
+                    previous: event.previousOwner,
+                    new: event.newOwner,
+                }
+            }
+            Some(&DisputeGameFactory::ImplementationSet::SIGNATURE_HASH) => {
+                let event = log
+                    .log_decode::<DisputeGameFactory::ImplementationSet>()?
+                    .inner
+                    .data;
+                Change::ImplementationSet {
+                    implementation: event.r#impl,
+                    game_type: event.gameType,
+                }
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(change))
+    }
+
+    /// The value the contract holds after this change.
+    pub fn new_value(&self) -> Address {
+        match self {
+            Change::Upgraded { implementation } => *implementation,
+            Change::AdminChanged { new, .. } => *new,
+            Change::OwnershipTransferred { new, .. } => *new,
+            Change::ImplementationSet { implementation, .. } => *implementation,
+        }
+    }
+
+    /// Call data of the view function reading the changed value back, which is also how
+    /// a check verifying that value is recognized.
+    pub fn getter(&self) -> Vec<u8> {
+        match self {
+            Change::Upgraded { .. } => Proxy::implementationCall {}.abi_encode(),
+            Change::AdminChanged { .. } => Proxy::adminCall {}.abi_encode(),
+            Change::OwnershipTransferred { .. } => Ownable::ownerCall {}.abi_encode(),
+            Change::ImplementationSet { game_type, .. } => DisputeGameFactory::gameImplsCall {
+                gameType: *game_type,
+            }
+            .abi_encode(),
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Upgraded { implementation } => write!(f, "Upgraded to {}", implementation),
+            Change::AdminChanged { previous, new } => {
+                write!(f, "AdminChanged {} -> {}", previous, new)
+            }
+            Change::OwnershipTransferred { previous, new } => {
+                write!(f, "OwnershipTransferred {} -> {}", previous, new)
+            }
+            Change::ImplementationSet {
+                implementation,
+                game_type,
+            } => write!(
+                f,
+                "ImplementationSet for game type {} to {}",
+                game_type, implementation
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ChangeEvent {
+    pub contract: Address,
+    pub block: u64,
+    pub timestamp: u64,
+    pub tx: Option<B256>,
+    pub change: Change,
+}
+
+/// Fetches every upgrade, admin, ownership and game implementation change emitted by
+/// `contracts` between `from` and `to` (inclusive), oldest first.
+///
+/// The range is split into chunks of `LOG_CHUNK_BLOCKS`, since most RPC providers cap
+/// the range of a single `eth_getLogs` call. Nothing is fetched without contracts.
+pub async fn fetch_changes(
+    provider: &ReqwestProvider,
+    contracts: &[Address],
+    from: u64,
+    to: u64,
+) -> Result<Vec<ChangeEvent>> {
+    // Without an address, the filter would match these events on every contract
+    if contracts.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let signatures = vec![
+        Proxy::Upgraded::SIGNATURE_HASH,
+        Proxy::AdminChanged::SIGNATURE_HASH,
+        Ownable::OwnershipTransferred::SIGNATURE_HASH,
+        DisputeGameFactory::ImplementationSet::SIGNATURE_HASH,
+    ];
+
+    let mut logs = Vec::new();
+    let mut start = from;
+    while start <= to {
+        let end = to.min(start.saturating_add(LOG_CHUNK_BLOCKS - 1));
+        let filter = Filter::new()
+            .address(contracts.to_vec())
+            .event_signature(signatures.clone())
+            .from_block(start)
+            .to_block(end);
+        logs.extend(provider.get_logs(&filter).await.context(format!(
+            "Failed to fetch logs for blocks {}..={}",
+            start, end
+        ))?);
+        start = end + 1;
+    }
+
+    let mut timestamps: HashMap<u64, u64> = HashMap::new();
+    let mut events = Vec::with_capacity(logs.len());
+    for log in &logs {
+        let Some(change) = Change::decode(log)? else {
+            continue;
+        };
+        let block = log
+            .block_number
+            .ok_or_else(|| anyhow!("RPC returned a log without a block number"))?;
+
+        let timestamp = match (log.block_timestamp, timestamps.get(&block)) {
+            (Some(timestamp), _) | (None, Some(&timestamp)) => timestamp,
+            (None, None) => {
+                provider
+                    .get_block(BlockId::number(block), BlockTransactionsKind::Hashes)
+                    .await
+                    .context(format!("Failed to fetch block {}", block))?
+                    .ok_or_else(|| anyhow!("Block {} not found", block))?
+                    .header
+                    .timestamp
+            }
+        };
+        timestamps.insert(block, timestamp);
+
+        events.push(ChangeEvent {
+            contract: log.address(),
+            block,
+            timestamp,
+            tx: log.transaction_hash,
+            change,
+        });
+    }
+
+    Ok(events)
+}
+
+/// For each event, whether it happened after the docs value it changes was last valid,
+/// i.e. whether no event from then on sets the value the docs record.
+///
+/// `documented` gives the recorded value for an event's contract and getter, if the docs
+/// record one; other events are never flagged.
+pub fn after_docs(
+    events: &[ChangeEvent],
+    documented: impl Fn(&ChangeEvent) -> Option<Address>,
+) -> Vec<bool> {
+    events
+        .iter()
+        .enumerate()
+        .map(|(i, event)| {
+            let Some(value) = documented(event) else {
+                return false;
+            };
+            let getter = event.change.getter();
+            !events[i..].iter().any(|later| {
+                later.contract == event.contract
+                    && later.change.getter() == getter
+                    && later.change.new_value() == value
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    const PORTAL: Address = address!("49048044D57e1C92A77f79988d21Fa8fAF74E97e");
+    const OLD: Address = address!("1111111111111111111111111111111111111111");
+    const NEW: Address = address!("2222222222222222222222222222222222222222");
+
+    fn upgraded(block: u64, implementation: Address) -> ChangeEvent {
+        ChangeEvent {
+            contract: PORTAL,
+            block,
+            timestamp: block * 12,
+            tx: None,
+            change: Change::Upgraded { implementation },
+        }
+    }
+
+    fn owner_changed(block: u64, new: Address) -> ChangeEvent {
+        ChangeEvent {
+            contract: PORTAL,
+            block,
+            timestamp: block * 12,
+            tx: None,
+            change: Change::OwnershipTransferred {
+                previous: Address::ZERO,
+                new,
+            },
+        }
+    }
+
+    fn admin_changed(block: u64, previous: Address, new: Address) -> ChangeEvent {
+        ChangeEvent {
+            contract: PORTAL,
+            block,
+            timestamp: block * 12,
+            tx: None,
+            change: Change::AdminChanged { previous, new },
+        }
+    }
+
+    fn documented_implementation(value: Address) -> impl Fn(&ChangeEvent) -> Option<Address> {
+        move |event| {
+            (event.change.getter() == Proxy::implementationCall {}.abi_encode()).then_some(value)
+        }
+    }
+
+    #[test]
+    fn flags_changes_after_the_documented_value() {
+        let events = [upgraded(1, OLD), upgraded(2, NEW)];
+        assert_eq!(
+            after_docs(&events, documented_implementation(OLD)),
+            [false, true]
+        );
+    }
+
+    #[test]
+    fn nothing_is_stale_when_the_latest_value_is_documented() {
+        let events = [upgraded(1, OLD), upgraded(2, NEW)];
+        assert_eq!(
+            after_docs(&events, documented_implementation(NEW)),
+            [false, false]
+        );
+    }
+
+    #[test]
+    fn a_later_revert_to_the_documented_value_clears_earlier_events() {
+        let events = [upgraded(1, NEW), upgraded(2, OLD), upgraded(3, NEW)];
+        assert_eq!(
+            after_docs(&events, documented_implementation(NEW)),
+            [false, false, false]
+        );
+    }
+
+    #[test]
+    fn flags_admin_changes_away_from_the_documented_proxy_admin() {
+        let events = [
+            admin_changed(1, Address::ZERO, OLD),
+            admin_changed(2, OLD, NEW),
+        ];
+        // The docs record OLD as the ProxyAdmin, which the second event replaced
+        let documented_admin = |event: &ChangeEvent| {
+            (event.change.getter() == Proxy::adminCall {}.abi_encode()).then_some(OLD)
+        };
+        assert_eq!(after_docs(&events, documented_admin), [false, true]);
+    }
+
+    #[test]
+    fn other_getters_are_compared_separately() {
+        let events = [upgraded(1, OLD), owner_changed(2, NEW)];
+        // Only implementation changes are documented, so the ownership change is never flagged
+        assert_eq!(
+            after_docs(&events, documented_implementation(OLD)),
+            [false, false]
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use alloy::eips::BlockId;
+use alloy::primitives::{Address, U256};
+use alloy::providers::{Provider, ReqwestProvider};
+use alloy::sol_types::SolCall;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::CheckResult;
+use crate::abi::{GnosisSafe, Multicall3};
+use crate::constants::KEY_TYPE_COLUMN;
+use crate::parser::{Contract, Network, NetworkRef};
+use crate::value::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyType {
+    Eoa,
+    GnosisSafe,
+    /// Has code, but does not answer the Safe probes
+    Contract,
+}
+
+impl fmt::Display for KeyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyType::Eoa => write!(f, "EOA"),
+            KeyType::GnosisSafe => write!(f, "Gnosis Safe"),
+            KeyType::Contract => write!(f, "non-Safe contract"),
+        }
+    }
+}
+
+impl KeyType {
+    /// Interprets the free-form "Type of Key" text from the docs.
+    fn from_doc(text: &str) -> Option<KeyType> {
+        let text = text.to_ascii_lowercase();
+        if text.contains("safe") {
+            Some(KeyType::GnosisSafe)
+        } else if text.starts_with("eoa") {
+            Some(KeyType::Eoa)
+        } else {
+            None
+        }
+    }
+}
+
+/// Classifies every row of `location` that has a "Type of Key" column and compares the
+/// documented key type with the on-chain account.
+///
+/// Accounts without code are EOAs. Accounts with code are Safes if both `getThreshold()`
+/// and `VERSION()` answer, and plain contracts otherwise. A row whose account cannot be
+/// read fails on its own, leaving the other rows to be compared.
+pub async fn verify_key_types(
+    provider: &ReqwestProvider,
+    multicall3: Address,
+    block: BlockId,
+    networks: &[Network],
+    location: NetworkRef<'_>,
+) -> Vec<CheckResult> {
+    let rows: Vec<(&Contract, &str)> = networks
+        .iter()
+        .filter(|n| n.location() == location)
+        .flat_map(|n| &n.contracts)
+        .filter_map(|c| {
+            c.column(KEY_TYPE_COLUMN)
+                .map(|cell| (c, cell.text.as_str()))
+        })
+        .collect();
+
+    // Whether each row's account has code, with the index of its first probe
+    let mut accounts: Vec<Result<(bool, usize), String>> = Vec::with_capacity(rows.len());
+    let mut calls = Vec::with_capacity(rows.len() * 2);
+
+    for (contract, _) in &rows {
+        let address = match Address::from_str(&contract.address) {
+            Ok(address) => address,
+            Err(e) => {
+                accounts.push(Err(format!("Error parsing address: {}", e)));
+                continue;
+            }
+        };
+        let code = match provider.get_code_at(address).block_id(block).await {
+            Ok(code) => code,
+            Err(e) => {
+                accounts.push(Err(format!("Failed to fetch code: {}", e)));
+                continue;
+            }
+        };
+
+        accounts.push(Ok((!code.is_empty(), calls.len())));
+        calls.push(Multicall3::Call3 {
+            target: address,
+            allowFailure: true,
+            callData: GnosisSafe::getThresholdCall {}.abi_encode().into(),
+        });
+        calls.push(Multicall3::Call3 {
+            target: address,
+            allowFailure: true,
+            callData: GnosisSafe::VERSIONCall {}.abi_encode().into(),
+        });
+    }
+
+    let probes = if calls.is_empty() {
+        Ok(vec![])
+    } else {
+        Multicall3::new(multicall3, provider.clone())
+            .aggregate3(calls)
+            .block(block)
+            .call()
+            .await
+            .map(|r| r.returnData)
+            .map_err(|e| format!("Safe probe multicall failed: {}", e))
+    };
+
+    let mut results = Vec::with_capacity(rows.len());
+
+    for ((contract, doc_text), account) in rows.iter().zip(accounts) {
+        let mut result =
+            CheckResult::new(format!("{} ({})", contract.name, KEY_TYPE_COLUMN), location);
+
+        let Some(documented) = KeyType::from_doc(doc_text) else {
+            result.error = Some(format!("Unrecognized key type '{}'", doc_text));
+            results.push(result);
+            continue;
+        };
+
+        let actual = match account {
+            Err(e) => Err(e),
+            Ok((false, _)) => Ok(KeyType::Eoa),
+            Ok((true, probe)) => match &probes {
+                Err(e) => Err(e.clone()),
+                Ok(probes) if is_safe(&probes[probe], &probes[probe + 1]) => {
+                    Ok(KeyType::GnosisSafe)
+                }
+                Ok(_) => Ok(KeyType::Contract),
+            },
+        };
+        result.expected = Some(Value::String(documented.to_string()));
+        match actual {
+            Err(e) => result.error = Some(e),
+            Ok(actual) => {
+                result.actual = Some(Value::String(actual.to_string()));
+                result.success = actual == documented;
+            }
+        }
+        results.push(result);
+    }
+
+    results
+}
+
+fn is_safe(threshold: &Multicall3::Result, version: &Multicall3::Result) -> bool {
+    if !threshold.success || !version.success {
+        return false;
+    }
+
+    let threshold = GnosisSafe::getThresholdCall::abi_decode_returns(&threshold.returnData, true)
+        .map(|r| r._0)
+        .unwrap_or(U256::ZERO);
+    let version = GnosisSafe::VERSIONCall::abi_decode_returns(&version.returnData, true);
+
+    threshold > U256::ZERO && version.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolValue;
+
+    fn returned(success: bool, data: Vec<u8>) -> Multicall3::Result {
+        Multicall3::Result {
+            success,
+            returnData: data.into(),
+        }
+    }
+
+    fn threshold(n: u64) -> Multicall3::Result {
+        returned(true, U256::from(n).abi_encode())
+    }
+
+    fn version() -> Multicall3::Result {
+        returned(true, "1.3.0".to_string().abi_encode())
+    }
+
+    #[test]
+    fn reads_the_documented_key_types() {
+        assert_eq!(
+            KeyType::from_doc("EOA managed by Coinbase Technologies"),
+            Some(KeyType::Eoa)
+        );
+        assert_eq!(
+            KeyType::from_doc("EOA (with no known private key)"),
+            Some(KeyType::Eoa)
+        );
+        assert_eq!(KeyType::from_doc("Gnosis Safe"), Some(KeyType::GnosisSafe));
+        assert_eq!(KeyType::from_doc("Hardware wallet"), None);
+    }
+
+    #[test]
+    fn safe_answers_both_probes() {
+        assert!(is_safe(&threshold(2), &version()));
+    }
+
+    #[test]
+    fn empty_returns_are_not_a_safe() {
+        // Calls to an account without code succeed with no return data
+        assert!(!is_safe(&returned(true, vec![]), &returned(true, vec![])));
+    }
+
+    #[test]
+    fn zero_threshold_is_not_a_safe() {
+        assert!(!is_safe(&threshold(0), &version()));
+    }
+
+    #[test]
+    fn reverting_version_is_not_a_safe() {
+        assert!(!is_safe(&threshold(2), &returned(false, vec![])));
+    }
+}
@@ -0,0 +1,178 @@
+use url::Url;
+
+use crate::constants::{ADMIN_SECTION, L1_CONTRACTS_SECTION, L2_CONTRACTS_SECTION};
+use crate::parser::{Contract, Network, NetworkRef};
+use crate::registry::NetworkConfig;
+
+#[derive(Debug)]
+pub struct LinkIssue {
+    pub name: String,
+    pub location: String,
+    pub line: usize,
+    pub message: String,
+}
+
+/// Explorer host expected for each docs table of the registry's networks.
+///
+/// Admin roles live on L1, so their links belong on the L1 explorer.
+pub fn registry_explorers(registry: &[NetworkConfig]) -> Vec<(NetworkRef<'_>, &str)> {
+    registry
+        .iter()
+        .flat_map(|n| {
+            [
+                (L2_CONTRACTS_SECTION, &n.l2_header, &n.l2_explorer),
+                (L1_CONTRACTS_SECTION, &n.l1_header, &n.l1_explorer),
+                (ADMIN_SECTION, &n.l2_header, &n.l1_explorer),
+            ]
+            .map(|(section, network, explorer)| {
+                (NetworkRef { section, network }, explorer.as_str())
+            })
+        })
+        .collect()
+}
+
+/// Checks that every row's explorer link points at the address in its label, and that
+/// the link uses the explorer configured for the chain the row lives on.
+///
+/// `explorers` maps a section/network pair to the expected explorer host. Rows in
+/// tables without an entry only get the address comparison.
+pub fn validate_links(networks: &[Network], explorers: &[(NetworkRef, &str)]) -> Vec<LinkIssue> {
+    let mut issues = Vec::new();
+
+    for network in networks {
+        let location = network.location();
+        let expected_host = explorers
+            .iter()
+            .find(|(loc, _)| *loc == location)
+            .map(|(_, host)| *host);
+
+        for contract in &network.contracts {
+            if let Err(message) = validate_link(contract, expected_host) {
+                issues.push(LinkIssue {
+                    name: contract.name.clone(),
+                    location: location.to_string(),
+                    line: contract.line,
+                    message,
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+fn validate_link(contract: &Contract, expected_host: Option<&str>) -> Result<(), String> {
+    let link = contract
+        .address_link()
+        .ok_or_else(|| "Address is not linked to an explorer".to_string())?;
+
+    let url = Url::parse(link).map_err(|e| format!("Invalid link {}: {}", link, e))?;
+
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|s| s.filter(|seg| !seg.is_empty()).collect())
+        .unwrap_or_default();
+    let linked_address = match segments.as_slice() {
+        [.., "address", addr] => *addr,
+        _ => return Err(format!("Link {} is not an explorer address page", link)),
+    };
+
+    if !linked_address.eq_ignore_ascii_case(&contract.address) {
+        return Err(format!(
+            "Link points at {} but the label is {}",
+            linked_address, contract.address
+        ));
+    }
+
+    if let Some(expected_host) = expected_host {
+        let host = url.host_str().unwrap_or_default();
+        if !host.eq_ignore_ascii_case(expected_host) {
+            return Err(format!(
+                "Link uses {} but this chain's explorer is {}",
+                host, expected_host
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_networks;
+    use crate::registry::{DEFAULT_NETWORKS, load_registry};
+
+    const ADDRESS: &str = "0x4200000000000000000000000000000000000007";
+    const OTHER: &str = "0x4200000000000000000000000000000000000010";
+
+    /// Link issues of a one-row table under `section` for the mainnet pair.
+    fn issues(section: &str, network: &str, cell: &str) -> Vec<LinkIssue> {
+        let doc = format!(
+            "## {}\n\n### {}\n\n| Name | Address |\n| - | - |\n| Row | {} |\n",
+            section, network, cell
+        );
+        let networks = parse_networks(&doc).unwrap();
+        let registry = load_registry(DEFAULT_NETWORKS).unwrap();
+        validate_links(&networks, &registry_explorers(&registry))
+    }
+
+    fn l2_row(cell: &str) -> Vec<LinkIssue> {
+        issues(L2_CONTRACTS_SECTION, "Base Mainnet", cell)
+    }
+
+    #[test]
+    fn accepts_matching_link_on_the_chain_explorer() {
+        let cell = format!("[{}](https://basescan.org/address/{})", ADDRESS, ADDRESS);
+        assert!(l2_row(&cell).is_empty());
+    }
+
+    #[test]
+    fn rejects_link_to_another_address() {
+        let cell = format!("[{}](https://basescan.org/address/{})", ADDRESS, OTHER);
+        let issues = l2_row(&cell);
+        assert_eq!(issues.len(), 1);
+        assert!(
+            issues[0].message.contains("Link points at"),
+            "{}",
+            issues[0].message
+        );
+        assert_eq!(issues[0].line, 7);
+    }
+
+    #[test]
+    fn rejects_link_on_another_chains_explorer() {
+        let cell = format!("[{}](https://etherscan.io/address/{})", ADDRESS, ADDRESS);
+        let issues = l2_row(&cell);
+        assert_eq!(issues.len(), 1);
+        assert!(
+            issues[0].message.contains("basescan.org"),
+            "{}",
+            issues[0].message
+        );
+    }
+
+    #[test]
+    fn rejects_links_to_non_address_pages() {
+        let cell = format!("[{}](https://basescan.org/token/{})", ADDRESS, ADDRESS);
+        let issues = l2_row(&cell);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("not an explorer address page"));
+    }
+
+    #[test]
+    fn rejects_unlinked_addresses() {
+        let issues = l2_row(ADDRESS);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].message, "Address is not linked to an explorer");
+    }
+
+    #[test]
+    fn expects_admin_rows_on_the_l1_explorer() {
+        let on_l1 = format!("[{}](https://etherscan.io/address/{})", ADDRESS, ADDRESS);
+        assert!(issues(ADMIN_SECTION, "Base Mainnet", &on_l1).is_empty());
+
+        let on_l2 = format!("[{}](https://basescan.org/address/{})", ADDRESS, ADDRESS);
+        assert_eq!(issues(ADMIN_SECTION, "Base Mainnet", &on_l2).len(), 1);
+    }
+}
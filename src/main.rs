@@ -1,21 +1,61 @@
-use alloy::primitives::Address;
-use alloy::providers::ProviderBuilder;
-use alloy::sol_types::SolCall;
+use alloy::eips::{BlockId, BlockNumberOrTag};
+use alloy::primitives::{Address, B256};
+use alloy::providers::{Provider, ProviderBuilder, ReqwestProvider};
+use alloy::rpc::types::BlockTransactionsKind;
 use anyhow::{Context, Result, anyhow};
 use clap::Parser;
-use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
 use std::fs;
 use std::path::PathBuf;
 use std::str::FromStr;
 
+use futures::future::join_all;
+
 mod abi;
-use abi::{
-    DisputeGameFactory, FaultDisputeGame, MIPS, Multicall3, PermissionedDisputeGame, SystemConfig,
+use abi::Multicall3;
+
+mod bisect;
+use bisect::Bisector;
+
+mod checks;
+use checks::{
+    CheckConfig, DEFAULT_CHECKS, DocNetwork, Expected, Target, load_checks, root_on_chain,
 };
 
+mod checksum;
+use checksum::{ChecksumStrictness, Severity, fix_checksums, lint_checksums};
+
 mod constants;
 use constants::*;
 
+mod derived;
+use derived::check_derivations;
+
+mod events;
+use events::{Change, ChangeEvent, after_docs, fetch_changes};
+
+mod keys;
+use keys::verify_key_types;
+
+mod links;
+use links::{registry_explorers, validate_links};
+
+mod registry;
+use registry::{DEFAULT_NETWORKS, Layer, NetworkConfig, load_registry};
+
+mod parser;
+use parser::{Contract, Network, NetworkRef, parse_networks};
+
+mod predeploys;
+use predeploys::{check_predeploys, verify_code_exists, verify_predeploy_implementations};
+
+mod proxies;
+use proxies::{documented_implementation, is_proxy_row, verify_proxy_admin, verify_proxy_slots};
+
+mod value;
+use value::Value;
+
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
@@ -23,87 +63,568 @@ struct Cli {
     #[arg(short, long, value_name = "FILE")]
     file: PathBuf,
 
-    /// Mainnet RPC URL
-    #[arg(long, value_name = "URL", env = MAINNET_RPC_URL_ENV)]
+    /// L1 RPC URL for a registry network, as NAME=URL (overrides the network's env var)
+    #[arg(long = "rpc-url", value_name = "NAME=URL", value_parser = parse_rpc_url)]
+    rpc_urls: Vec<(String, String)>,
+
+    /// L2 RPC URL for a registry network, as NAME=URL (overrides the network's L2 env var)
+    #[arg(long = "l2-rpc-url", value_name = "NAME=URL", value_parser = parse_rpc_url)]
+    l2_rpc_urls: Vec<(String, String)>,
+
+    /// Ethereum Mainnet RPC URL, shorthand for --rpc-url mainnet=URL
+    #[arg(long, value_name = "URL")]
     mainnet_rpc_url: Option<String>,
 
-    /// Sepolia RPC URL
-    #[arg(long, value_name = "URL", env = SEPOLIA_RPC_URL_ENV)]
+    /// Ethereum Sepolia RPC URL, shorthand for --rpc-url sepolia=URL
+    #[arg(long, value_name = "URL")]
     sepolia_rpc_url: Option<String>,
+
+    /// Base Mainnet RPC URL, shorthand for --l2-rpc-url mainnet=URL
+    #[arg(long, value_name = "URL")]
+    base_mainnet_rpc_url: Option<String>,
+
+    /// Base Sepolia RPC URL, shorthand for --l2-rpc-url sepolia=URL
+    #[arg(long, value_name = "URL")]
+    base_sepolia_rpc_url: Option<String>,
+
+    /// Block to verify at (number, hash, latest, safe or finalized), as [NAME=]BLOCK.
+    /// Without NAME it applies to every network
+    #[arg(long = "block", value_name = "[NAME=]BLOCK", value_parser = parse_block)]
+    blocks: Vec<(Option<String>, BlockId)>,
+
+    /// L2 block to verify at, as [NAME=]BLOCK like --block
+    #[arg(long = "l2-block", value_name = "[NAME=]BLOCK", value_parser = parse_block)]
+    l2_blocks: Vec<(Option<String>, BlockId)>,
+
+    /// Find the first block at which CHECK stopped matching the docs instead of verifying.
+    /// Needs an archive RPC and --bisect-from
+    #[arg(
+        long,
+        value_name = "CHECK",
+        requires = "bisect_from",
+        conflicts_with_all = ["scan_events", "fix_checksums"]
+    )]
+    bisect: Option<String>,
+
+    /// Block at which the bisected check still matches, as [NAME=]BLOCK
+    #[arg(long, value_name = "[NAME=]BLOCK", value_parser = parse_block, requires = "bisect")]
+    bisect_from: Vec<(Option<String>, BlockId)>,
+
+    /// Print the upgrade and ownership change timeline of the L1 contracts instead of
+    /// verifying, from --events-from up to --block
+    #[arg(long, requires = "events_from", conflicts_with = "fix_checksums")]
+    scan_events: bool,
+
+    /// First block scanned for change events, as [NAME=]BLOCK
+    #[arg(long, value_name = "[NAME=]BLOCK", value_parser = parse_block, requires = "scan_events")]
+    events_from: Vec<(Option<String>, BlockId)>,
+
+    /// TOML file of networks to verify, replacing the built-in registry
+    #[arg(long, value_name = "FILE")]
+    networks: Option<PathBuf>,
+
+    /// How to treat addresses written without EIP-55 checksum casing
+    #[arg(long, value_enum, default_value_t = ChecksumStrictness::Warn)]
+    checksum_strictness: ChecksumStrictness,
+
+    /// Rewrite all-lowercase/all-uppercase addresses in FILE to their EIP-55 form and exit
+    #[arg(long)]
+    fix_checksums: bool,
+
+    /// TOML file of check definitions, replacing the built-in checks
+    #[arg(long, value_name = "FILE")]
+    checks: Option<PathBuf>,
+
+    /// Trust only the documented SystemConfig and resolve every other call target on-chain
+    #[arg(long)]
+    chain_rooted: bool,
 }
 
 #[derive(Debug)]
-struct Contract {
+struct CheckResult {
     name: String,
-    address: String,
+    network: String,
+    expected: Option<Value>,
+    actual: Option<Value>,
+    success: bool,
+    error: Option<String>,
+    /// Docs row the check needed but could not find, so it was never run or its result
+    /// could not be compared
+    missing_dependency: Option<String>,
+    /// How the expected value was derived, when it is not the documented value itself
+    expected_note: Option<String>,
+}
+
+impl CheckResult {
+    /// A result for check `name` on `network` that has not passed yet.
+    fn new(name: impl Into<String>, network: impl fmt::Display) -> Self {
+        CheckResult {
+            name: name.into(),
+            network: network.to_string(),
+            expected: None,
+            actual: None,
+            success: false,
+            error: None,
+            missing_dependency: None,
+            expected_note: None,
+        }
+    }
+}
+
+/// Why a check's expected value is unknown.
+enum Unresolved {
+    /// The value comes from a docs row that is missing, directly or through another check
+    MissingRow(String),
+    Error(String),
 }
 
+/// Outcome of verifying one network, along with the block it was verified at.
 #[derive(Debug)]
-struct Network {
-    name: String,
-    contracts: Vec<Contract>,
+struct NetworkReport {
+    block_number: u64,
+    block_hash: B256,
+    results: Vec<CheckResult>,
 }
 
+/// The RPC given for a network serves a different chain than the registry expects.
 #[derive(Debug)]
-struct CheckResult {
+struct ChainIdMismatch {
     name: String,
-    network: String,
-    expected: Option<Address>,
-    actual: Option<Address>,
-    success: bool,
-    error: Option<String>,
+    flag: &'static str,
+    rpc_env: String,
+    expected: u64,
+    actual: u64,
+}
+
+impl fmt::Display for ChainIdMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RPC reports chain id {} but {} is expected; check {} {}=<URL> or {}",
+            self.actual, self.expected, self.flag, self.name, self.rpc_env
+        )
+    }
 }
 
-type Decoder = Box<dyn Fn(&[u8]) -> Result<Address> + Send + Sync>;
+impl std::error::Error for ChainIdMismatch {}
+
+/// The block range given for a network ends before it starts.
+#[derive(Debug)]
+struct InvalidBlockRange {
+    from: u64,
+    to: u64,
+}
+
+impl fmt::Display for InvalidBlockRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "start block {} is after end block {}; check --events-from and --block",
+            self.from, self.to
+        )
+    }
+}
+
+impl std::error::Error for InvalidBlockRange {}
+
+/// Whether an error stems from the command-line configuration rather than the chain.
+fn is_config_error(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<ChainIdMismatch>().is_some() || e.downcast_ref::<InvalidBlockRange>().is_some()
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
 
     let content = fs::read_to_string(&cli.file)
         .with_context(|| format!("Failed to read input file: {:?}", cli.file))?;
 
     let networks = parse_networks(&content)?;
 
-    // Verification Logic
+    let mut checks = match &cli.checks {
+        Some(path) => {
+            let source = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read check file: {:?}", path))?;
+            load_checks(&source).with_context(|| format!("Failed to load {:?}", path))?
+        }
+        None => load_checks(DEFAULT_CHECKS).context("Failed to load built-in checks")?,
+    };
+    let registry = match &cli.networks {
+        Some(path) => {
+            let source = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read network registry: {:?}", path))?;
+            load_registry(&source).with_context(|| format!("Failed to load {:?}", path))?
+        }
+        None => load_registry(DEFAULT_NETWORKS).context("Failed to load built-in networks")?,
+    };
+    // The shorthands name the built-in registry entries; --rpc-url and --l2-rpc-url take
+    // precedence
+    for (flag, name, layer, url) in [
+        (
+            "--sepolia-rpc-url",
+            "sepolia",
+            Layer::L1,
+            cli.sepolia_rpc_url.take(),
+        ),
+        (
+            "--mainnet-rpc-url",
+            "mainnet",
+            Layer::L1,
+            cli.mainnet_rpc_url.take(),
+        ),
+        (
+            "--base-sepolia-rpc-url",
+            "sepolia",
+            Layer::L2,
+            cli.base_sepolia_rpc_url.take(),
+        ),
+        (
+            "--base-mainnet-rpc-url",
+            "mainnet",
+            Layer::L2,
+            cli.base_mainnet_rpc_url.take(),
+        ),
+    ] {
+        let Some(url) = url else {
+            continue;
+        };
+        if !registry.iter().any(|n| n.name == name) {
+            return Err(anyhow!(
+                "{} needs a registry network named '{}'",
+                flag,
+                name
+            ));
+        }
+        let urls = match layer {
+            Layer::L1 => &mut cli.rpc_urls,
+            Layer::L2 => &mut cli.l2_rpc_urls,
+        };
+        urls.insert(0, (name.to_string(), url));
+    }
+    for (flag, urls) in [
+        ("--rpc-url", &cli.rpc_urls),
+        ("--l2-rpc-url", &cli.l2_rpc_urls),
+    ] {
+        for (name, _) in urls {
+            if !registry.iter().any(|n| &n.name == name) {
+                return Err(anyhow!("{} names unknown network '{}'", flag, name));
+            }
+        }
+    }
+    for (flag, blocks) in [
+        ("--block", &cli.blocks),
+        ("--l2-block", &cli.l2_blocks),
+        ("--bisect-from", &cli.bisect_from),
+        ("--events-from", &cli.events_from),
+    ] {
+        for name in blocks.iter().filter_map(|(name, _)| name.as_ref()) {
+            if !registry.iter().any(|n| &n.name == name) {
+                return Err(anyhow!("{} names unknown network '{}'", flag, name));
+            }
+        }
+    }
+
+    if cli.chain_rooted {
+        root_on_chain(&mut checks, CHAIN_ROOT_ROW).context("Cannot run in chain-rooted mode")?;
+    }
+
+    if cli.fix_checksums {
+        let issues = lint_checksums(&networks, ChecksumStrictness::Strict);
+        let (fixed_content, fixed) = fix_checksums(&content, &issues);
+        fs::write(&cli.file, fixed_content)
+            .with_context(|| format!("Failed to write input file: {:?}", cli.file))?;
+        println!("Rewrote {} address(es) in {}", fixed, cli.file.display());
+
+        // Invalid mixed-case checksums may be typos, so they are never rewritten
+        let mut exit_code = 0;
+        for issue in issues.iter().filter(|i| !i.uncased) {
+            exit_code = 1;
+            println!(
+                "❌ {}:{}: {} has an invalid checksum, not rewritten (checksummed: {})",
+                cli.file.display(),
+                issue.line,
+                issue.address,
+                issue.checksummed
+            );
+        }
+        std::process::exit(exit_code);
+    }
+
+    let rpc_urls: Vec<Option<String>> = registry
+        .iter()
+        .map(|n| rpc_url_for(&cli.rpc_urls, n, Layer::L1))
+        .collect();
+
+    if let Some(name) = &cli.bisect {
+        let check = checks
+            .iter()
+            .find(|c| &c.name == name)
+            .ok_or_else(|| anyhow!("--bisect names unknown check '{}'", name))?;
+        if check.layer != Layer::L1 {
+            return Err(anyhow!("--bisect only supports L1 checks"));
+        }
+        // The expected value would have to be bisected along with the check's own
+        if let Expected::Check(other) = &check.expected {
+            return Err(anyhow!(
+                "--bisect is not supported for expected_check checks: '{}' compares against check '{}'",
+                check.name,
+                other
+            ));
+        }
+
+        let mut exit_code = 0;
+        let mut without_start = false;
+        let mut bisected = false;
+        for (network, rpc_url) in registry.iter().zip(&rpc_urls) {
+            let Some(rpc_url) = rpc_url else {
+                println!(
+                    "Skipped bisecting on {}: no RPC URL, set {} or pass --rpc-url {}=<URL>",
+                    network.l1_header, network.rpc_env, network.name
+                );
+                continue;
+            };
+            // A freshly deployed proxy is uninitialized, so no default start block is safe
+            let Some(from) = block_for(&cli.bisect_from, &network.name) else {
+                println!(
+                    "Skipped bisecting on {}: no start block, pass --bisect-from {}=<BLOCK> where {} still matches",
+                    network.l1_header, network.name, check.name
+                );
+                without_start = true;
+                continue;
+            };
+            bisected = true;
+            let to = block_for(&cli.blocks, &network.name).unwrap_or_else(BlockId::latest);
+            match bisect_network(&networks, &checks, check, network, rpc_url, from, to).await {
+                Ok(()) => {}
+                Err(e) if is_config_error(&e) => {
+                    eprintln!("❌ Configuration error for {}: {:#}", network.l1_header, e);
+                    exit_code = CONFIG_ERROR_EXIT_CODE;
+                }
+                Err(e) => {
+                    eprintln!(
+                        "❌ Error bisecting {} on {}: {:#}",
+                        check.name, network.l1_header, e
+                    );
+                    exit_code = exit_code.max(1);
+                }
+            }
+        }
+        if without_start && !bisected {
+            eprintln!(
+                "❌ No network with an RPC URL has a block to bisect {} from: pass --bisect-from NAME=<BLOCK>",
+                check.name
+            );
+            exit_code = exit_code.max(1);
+        }
+        std::process::exit(exit_code);
+    }
+
+    if cli.scan_events {
+        let mut exit_code = 0;
+        let mut without_start = false;
+        let mut scanned = false;
+        for (network, rpc_url) in registry.iter().zip(&rpc_urls) {
+            let Some(rpc_url) = rpc_url else {
+                println!(
+                    "Skipped scanning {}: no RPC URL, set {} or pass --rpc-url {}=<URL>",
+                    network.l1_header, network.rpc_env, network.name
+                );
+                continue;
+            };
+            // Scanning from genesis takes thousands of log queries, so there is no default
+            let Some(from) = block_for(&cli.events_from, &network.name) else {
+                println!(
+                    "Skipped scanning {}: no start block, pass --events-from {}=<BLOCK>",
+                    network.l1_header, network.name
+                );
+                without_start = true;
+                continue;
+            };
+            scanned = true;
+            let to = block_for(&cli.blocks, &network.name).unwrap_or_else(BlockId::latest);
+            match scan_network(&networks, &checks, network, rpc_url, from, to).await {
+                Ok(()) => {}
+                Err(e) if is_config_error(&e) => {
+                    eprintln!("❌ Configuration error for {}: {:#}", network.l1_header, e);
+                    exit_code = CONFIG_ERROR_EXIT_CODE;
+                }
+                Err(e) => {
+                    eprintln!("❌ Error scanning {}: {:#}", network.l1_header, e);
+                    exit_code = exit_code.max(1);
+                }
+            }
+        }
+        if without_start && !scanned {
+            eprintln!(
+                "❌ No network with an RPC URL has a block to scan from: pass --events-from NAME=<BLOCK>"
+            );
+            exit_code = exit_code.max(1);
+        }
+        std::process::exit(exit_code);
+    }
+
+    let mut exit_code = 0;
+
+    // Offline checksum lint
     println!("\n---------------------------------------------------------------------------");
-    println!("Verifying addresses...");
+    println!("Linting address checksums...");
     println!("---------------------------------------------------------------------------");
 
-    let mainnet_task = verify_network(
-        &networks,
-        ETHEREUM_MAINNET,
-        BASE_MAINNET,
-        cli.mainnet_rpc_url,
-    );
+    let checksum_issues = lint_checksums(&networks, cli.checksum_strictness);
+    if checksum_issues.is_empty() {
+        println!("✅ All addresses are EIP-55 checksummed");
+    }
+    for issue in &checksum_issues {
+        let (icon, problem) = match (issue.severity, issue.uncased) {
+            (Severity::Error, false) => ("❌", "invalid EIP-55 checksum"),
+            (Severity::Error, true) => ("❌", "missing EIP-55 checksum"),
+            (Severity::Warning, _) => ("⚠️ ", "missing EIP-55 checksum"),
+        };
+        if issue.severity == Severity::Error {
+            exit_code = 1;
+        }
+        println!(
+            "{} {}:{}: {} for {} ({}, expected {})",
+            icon,
+            cli.file.display(),
+            issue.line,
+            problem,
+            issue.name,
+            issue.address,
+            issue.checksummed
+        );
+    }
 
-    let sepolia_task = verify_network(
-        &networks,
-        ETHEREUM_SEPOLIA,
-        BASE_SEPOLIA,
-        cli.sepolia_rpc_url,
-    );
+    // Offline link validation
+    println!("\n---------------------------------------------------------------------------");
+    println!("Validating explorer links...");
+    println!("---------------------------------------------------------------------------");
 
-    let (mainnet_res, sepolia_res) = tokio::join!(mainnet_task, sepolia_task);
+    let explorers = registry_explorers(&registry);
+    let link_issues = validate_links(&networks, &explorers);
+    if link_issues.is_empty() {
+        println!("✅ All explorer links are valid");
+    } else {
+        exit_code = 1;
+        for issue in &link_issues {
+            println!(
+                "❌ BAD LINK for {} ({}, line {}): {}",
+                issue.name, issue.location, issue.line, issue.message
+            );
+        }
+    }
 
-    let mut exit_code = 0;
+    // Offline predeploy table
+    println!("\n---------------------------------------------------------------------------");
+    println!("Checking L2 predeploys...");
+    println!("---------------------------------------------------------------------------");
 
-    for (res, network_name) in [
-        (mainnet_res, ETHEREUM_MAINNET),
-        (sepolia_res, ETHEREUM_SEPOLIA),
-    ] {
+    let predeploy_issues = check_predeploys(&networks, &registry);
+    if predeploy_issues.is_empty() {
+        println!("✅ All L2 rows match the canonical predeploys");
+    }
+    for issue in &predeploy_issues {
+        let icon = match issue.severity {
+            Severity::Error => "❌",
+            Severity::Warning => "⚠️ ",
+        };
+        if issue.severity == Severity::Error {
+            exit_code = 1;
+        }
+        match issue.line {
+            Some(line) => println!(
+                "{} {} ({}, line {}): {}",
+                icon, issue.name, issue.location, line, issue.message
+            ),
+            None => println!(
+                "{} {} ({}): {}",
+                icon, issue.name, issue.location, issue.message
+            ),
+        }
+    }
+
+    // Offline derivation rules
+    println!("\n---------------------------------------------------------------------------");
+    println!("Checking derived addresses...");
+    println!("---------------------------------------------------------------------------");
+
+    let derivation_issues = check_derivations(&networks, &registry);
+    if derivation_issues.is_empty() {
+        println!("✅ All derivable addresses match the docs");
+    } else {
+        exit_code = 1;
+        for issue in &derivation_issues {
+            println!(
+                "❌ BAD DERIVATION for {} ({}, line {}): documented {}, expected {} from {}",
+                issue.name,
+                issue.location,
+                issue.line,
+                issue.documented,
+                issue.derived,
+                issue.source
+            );
+        }
+    }
+
+    // Verification Logic
+    println!("\n---------------------------------------------------------------------------");
+    if cli.chain_rooted {
+        println!(
+            "Verifying addresses (chain-rooted: L1 checks take only {} from the docs; L2 checks, \
+             key types and proxy admins still use the documented addresses)...",
+            CHAIN_ROOT_ROW
+        );
+    } else {
+        println!("Verifying addresses...");
+    }
+    println!("---------------------------------------------------------------------------");
+
+    // --block pins the L1 and --l2-block the L2, since block numbers differ per chain
+    let targets: Vec<(&NetworkConfig, Layer, Option<String>, BlockId)> = registry
+        .iter()
+        .zip(&rpc_urls)
+        .flat_map(|(n, rpc_url)| {
+            let block = block_for(&cli.blocks, &n.name).unwrap_or_else(BlockId::latest);
+            [
+                (n, Layer::L1, rpc_url.clone(), block),
+                (
+                    n,
+                    Layer::L2,
+                    rpc_url_for(&cli.l2_rpc_urls, n, Layer::L2),
+                    block_for(&cli.l2_blocks, &n.name).unwrap_or_else(BlockId::latest),
+                ),
+            ]
+        })
+        .collect();
+
+    let tasks = targets.iter().map(|(network, layer, rpc_url, block)| {
+        verify_network(&networks, &checks, network, *layer, rpc_url.clone(), *block)
+    });
+    let network_results = join_all(tasks).await;
+
+    let mut config_error = false;
+
+    for (res, (network, layer, _, _)) in network_results.into_iter().zip(&targets) {
+        let network_name = network.header(*layer);
         match res {
-            Ok(results) => {
-                if results.is_empty() {
-                    println!(
-                        "Skipped verification for {} (No RPC URL or addresses found)",
-                        network_name
-                    );
-                    continue;
-                }
+            Ok(None) => {
+                println!(
+                    "Skipped verification for {} (chain id {}): no RPC URL, set {} or pass {} {}=<URL>",
+                    network_name,
+                    network.chain_id(*layer),
+                    network.rpc_env(*layer),
+                    layer.rpc_flag(),
+                    network.name
+                );
+            }
+            Ok(Some(report)) => {
+                println!(
+                    "🔗 {} verified at block {} ({})",
+                    network_name, report.block_number, report.block_hash
+                );
 
                 let mut network_passed = true;
-                for check in results {
+                for check in report.results {
                     if !check.success {
                         network_passed = false;
                         exit_code = 1;
@@ -112,9 +633,13 @@ async fn main() -> Result<()> {
                 }
 
                 if network_passed {
-                    println!("✅ All addresses match for {}", network_name);
+                    println!("✅ All checks passed for {}", network_name);
                 }
             }
+            Err(e) if is_config_error(&e) => {
+                eprintln!("❌ Configuration error for {}: {:#}", network_name, e);
+                config_error = true;
+            }
             Err(e) => {
                 eprintln!("❌ Error verifying {}: {:#}", network_name, e);
                 exit_code = 1;
@@ -122,16 +647,30 @@ async fn main() -> Result<()> {
         }
     }
 
+    // A wrong RPC makes every other result meaningless, so it takes precedence
+    if config_error {
+        eprintln!("\n❌ One or more RPC URLs point at the wrong chain.");
+        std::process::exit(CONFIG_ERROR_EXIT_CODE);
+    }
+
     if exit_code == 0 {
         println!("\n✅ All checks passed successfully.");
     } else {
-        eprintln!("\n❌ Verification failed for one or more networks.");
+        eprintln!("\n❌ One or more checks failed.");
     }
 
     std::process::exit(exit_code);
 }
 
 fn print_failure(check: &CheckResult) {
+    if let Some(dependency) = &check.missing_dependency {
+        println!(
+            "⏭️  SKIPPED for {} ({}): missing dependency {}",
+            check.name, check.network, dependency
+        );
+        return;
+    }
+
     if let Some(error) = &check.error {
         println!("❌ ERROR for {}: {}", check.name, error);
         return;
@@ -139,358 +678,645 @@ fn print_failure(check: &CheckResult) {
 
     let expected = check
         .expected
-        .map(|a| a.to_string())
+        .as_ref()
+        .map(|v| v.to_string())
         .unwrap_or_else(|| "Unknown".to_string());
     let actual = check
         .actual
-        .map(|a| a.to_string())
+        .as_ref()
+        .map(|v| v.to_string())
         .unwrap_or_else(|| "Unknown".to_string());
 
+    let note = check
+        .expected_note
+        .as_ref()
+        .map(|note| format!(" ({})", note))
+        .unwrap_or_default();
+
     println!(
-        "❌ MISMATCH for {} ({}): \n\tFile: {}\n\tChain: {}",
-        check.name, check.network, expected, actual
+        "❌ MISMATCH for {} ({}): \n\tExpected: {}{}\n\tChain: {}",
+        check.name, check.network, expected, note, actual
     );
 }
 
-fn parse_networks(content: &str) -> Result<Vec<Network>> {
-    // Regex to capture network headers (lines starting with ###)
-    let network_re = Regex::new(r"^###\s+(?P<network>.+)")?;
-
-    // Regex to capture contract name and address
-    // Matches lines starting with | (optional), then name column, then address column containing [address]
-    let contract_re =
-        Regex::new(r"\|\s*(?P<name>[^|]+?)\s*\|\s*\[(?P<address>0x[a-fA-F0-9]{40})\]")?;
-
-    let mut networks: Vec<Network> = Vec::new();
-    let mut current_network_name: Option<String> = None;
-
-    for (line_num, line) in content.lines().enumerate() {
-        // Check for network header
-        if let Some(caps) = network_re.captures(line) {
-            let network_name = caps
-                .name("network")
-                .map(|m| m.as_str().trim().to_string())
-                .ok_or_else(|| {
-                    anyhow!("Failed to capture network name on line {}", line_num + 1)
-                })?;
-            current_network_name = Some(network_name);
+/// Searches the blocks between `from` (where `check` must match the docs) and `to` for
+/// the first block at which it stopped matching, and prints the outcome.
+async fn bisect_network(
+    networks: &[Network],
+    checks: &[CheckConfig],
+    check: &CheckConfig,
+    network: &NetworkConfig,
+    rpc_url: &str,
+    from: BlockId,
+    to: BlockId,
+) -> Result<()> {
+    let l1_contracts = doc_location(network, DocNetwork::L1Contracts);
+    let location = doc_location(network, doc_network_of(check));
+    let root = get_addr(networks, l1_contracts, check.root(checks)?)?;
+    let expected =
+        resolve_expected(networks, network, location, &check.expected).map_err(|e| anyhow!(e))?;
+    let (expected, _) = check.convert_expected(expected)?;
+
+    let provider = connect(network, Layer::L1, rpc_url).await?;
+    let block_number = |block: BlockId| {
+        let provider = provider.clone();
+        async move {
+            provider
+                .get_block(block, BlockTransactionsKind::Hashes)
+                .await
+                .context(format!("Failed to fetch block {}", block))?
+                .map(|b| b.header.number)
+                .ok_or_else(|| anyhow!("Block {} not found", block))
+        }
+    };
+    let good = block_number(from).await?;
+    let bad = block_number(to).await?;
+
+    let bisector = Bisector {
+        provider: &provider,
+        multicall3: network.multicall3,
+        checks,
+        check,
+        root,
+        expected: &expected,
+    };
+    println!(
+        "🔎 Bisecting {} on {} between blocks {} and {}...",
+        check.name, network.l1_header, good, bad
+    );
+    match bisector.run(good, bad).await? {
+        None => println!(
+            "✅ {} still matches the docs at block {} on {}",
+            check.name, bad, network.l1_header
+        ),
+        Some(divergence) => println!(
+            "❌ {} first differs from the docs at block {} (timestamp {}) on {}\n\tExpected: {}\n\tChain: {}\n\tLast matching block: {}",
+            check.name,
+            divergence.block,
+            divergence.timestamp,
+            network.l1_header,
+            expected,
+            divergence.observed,
+            divergence.block - 1
+        ),
+    }
+
+    Ok(())
+}
+
+/// Prints the change events of every documented L1 contract between `from` and `to`,
+/// flagging those after which the docs no longer match a value a check verifies.
+async fn scan_network(
+    networks: &[Network],
+    checks: &[CheckConfig],
+    network: &NetworkConfig,
+    rpc_url: &str,
+    from: BlockId,
+    to: BlockId,
+) -> Result<()> {
+    let l1_contracts = doc_location(network, DocNetwork::L1Contracts);
+    let contracts: Vec<(&Contract, Address)> = networks
+        .iter()
+        .filter(|n| n.location() == l1_contracts)
+        .flat_map(|n| &n.contracts)
+        .filter_map(|c| Some((c, Address::from_str(&c.address).ok()?)))
+        .collect();
+    let addresses: Vec<Address> = contracts.iter().map(|(_, address)| *address).collect();
+
+    let provider = connect(network, Layer::L1, rpc_url).await?;
+    let mut range = Vec::with_capacity(2);
+    for block in [from, to] {
+        range.push(
+            provider
+                .get_block(block, BlockTransactionsKind::Hashes)
+                .await
+                .context(format!("Failed to fetch block {}", block))?
+                .ok_or_else(|| anyhow!("Block {} not found", block))?
+                .header
+                .number,
+        );
+    }
+
+    if range[0] > range[1] {
+        return Err(InvalidBlockRange {
+            from: range[0],
+            to: range[1],
+        }
+        .into());
+    }
+
+    println!(
+        "\n📜 Change events on {} between blocks {} and {}",
+        network.l1_header, range[0], range[1]
+    );
+    let events = fetch_changes(&provider, &addresses, range[0], range[1]).await?;
+
+    let documented = |event: &ChangeEvent| documented_change(networks, checks, network, event);
+    let stale = after_docs(&events, |e| documented(e).map(|(_, address)| address));
+
+    for (contract, address) in &contracts {
+        let mut contract_events = events
+            .iter()
+            .zip(&stale)
+            .filter(|(e, _)| e.contract == *address)
+            .peekable();
+        if contract_events.peek().is_none() {
             continue;
         }
 
-        // Check for contract
-        if let Some(caps) = contract_re.captures(line) {
-            let name = caps
-                .name("name")
-                .map(|m| m.as_str().trim().to_string())
-                .ok_or_else(|| {
-                    anyhow!("Failed to capture contract name on line {}", line_num + 1)
-                })?;
-
-            let address = caps
-                .name("address")
-                .map(|m| m.as_str().to_string())
-                .ok_or_else(|| {
-                    anyhow!(
-                        "Failed to capture contract address on line {}",
-                        line_num + 1
-                    )
-                })?;
-
-            let net_name = current_network_name.clone().ok_or_else(|| {
-                anyhow!(
-                    "Found contract definition before network header on line {}",
-                    line_num + 1
-                )
-            })?;
+        println!("{} ({})", contract.name, address);
+        for (event, stale) in contract_events {
+            let tx = event
+                .tx
+                .map(|tx| format!(", tx {}", tx))
+                .unwrap_or_default();
+            print!(
+                "\tblock {} (timestamp {}{}): {}",
+                event.block, event.timestamp, tx, event.change
+            );
+            match documented(event) {
+                Some((check, _)) if *stale => {
+                    println!("\n\t⚠️  after the docs value for {} was last valid", check)
+                }
+                _ => println!(),
+            }
+        }
+    }
+    if events.is_empty() {
+        println!("No change events for the documented contracts");
+    }
+
+    Ok(())
+}
 
-            // Find existing network group or create new one
-            if let Some(pos) = networks.iter().position(|n| n.name == net_name) {
-                networks[pos].contracts.push(Contract { name, address });
-            } else {
-                networks.push(Network {
-                    name: net_name,
-                    contracts: vec![Contract { name, address }],
-                });
+/// Docs value that an L1 change event moves away from, with the check or docs row it
+/// belongs to, or `None` if the docs do not record the changed value.
+///
+/// The docs value of a changed field is the one the check reading it expects. Proxy
+/// admins, implementations and the ProxyAdmin owner are recorded by the docs even without
+/// such a check.
+fn documented_change(
+    networks: &[Network],
+    checks: &[CheckConfig],
+    network: &NetworkConfig,
+    event: &ChangeEvent,
+) -> Option<(String, Address)> {
+    let l1_contracts = doc_location(network, DocNetwork::L1Contracts);
+    let getter = event.change.getter();
+    let checked = checks
+        .iter()
+        .filter(|c| c.layer == Layer::L1 && c.call_data == getter)
+        .filter(|c| {
+            target_doc_row(checks, c).and_then(|row| get_addr(networks, l1_contracts, row).ok())
+                == Some(event.contract)
+        })
+        .find_map(|c| {
+            let location = doc_location(network, doc_network_of(c));
+            let expected = resolve_expected(networks, network, location, &c.expected).ok()?;
+            match c.convert_expected(expected).ok()? {
+                (Value::Address(address), _) => Some((c.name.clone(), address)),
+                _ => None,
             }
+        });
+    if checked.is_some() {
+        return checked;
+    }
+
+    let contract = networks
+        .iter()
+        .filter(|n| n.location() == l1_contracts)
+        .flat_map(|n| &n.contracts)
+        .find(|c| Address::from_str(&c.address).ok() == Some(event.contract))?;
+    match event.change {
+        Change::AdminChanged { .. } if is_proxy_row(&contract.name) => {
+            let admin = get_addr(networks, l1_contracts, PROXY_ADMIN_ROW).ok()?;
+            Some((PROXY_ADMIN_ROW.to_string(), admin))
+        }
+        Change::Upgraded { .. } => {
+            let implementation = documented_implementation(contract)?.ok()?;
+            Some((
+                format!("{} {}", contract.name, IMPLEMENTATION_COLUMN),
+                implementation,
+            ))
+        }
+        Change::OwnershipTransferred { .. }
+            if contract.name.eq_ignore_ascii_case(PROXY_ADMIN_ROW) =>
+        {
+            let admin_roles = doc_location(network, DocNetwork::AdminRoles);
+            let owner = get_addr(networks, admin_roles, PROXY_ADMIN_OWNER_ROW).ok()?;
+            Some((PROXY_ADMIN_OWNER_ROW.to_string(), owner))
         }
+        _ => None,
     }
-    Ok(networks)
 }
 
+/// Runs the checks of `layer` against one chain of a registry entry, followed by the
+/// layer's built-in verifications. Returns `None` if the chain has no RPC URL.
 async fn verify_network(
     networks: &[Network],
-    l1_network_name: &str,
-    l2_network_name: &str,
+    checks: &[CheckConfig],
+    network: &NetworkConfig,
+    layer: Layer,
     rpc_url: Option<String>,
-) -> Result<Vec<CheckResult>> {
+    block: BlockId,
+) -> Result<Option<NetworkReport>> {
     let rpc_url = match rpc_url {
         Some(url) => url,
-        None => return Ok(vec![]),
+        None => return Ok(None),
     };
 
-    // Fail fast if we can't find the configuration addresses needed for lookup
-    let sys_config = get_addr(networks, l1_network_name, "SystemConfig")?;
-    let dispute_game_factory = get_addr(networks, l1_network_name, "DisputeGameFactoryProxy")?;
-    let fault_dispute_game = get_addr(networks, l1_network_name, "FaultDisputeGame")?;
-    let permissioned_dispute_game = get_addr(networks, l1_network_name, "PermissionedDisputeGame")?;
-    let mips = get_addr(networks, l1_network_name, "MIPS")?;
+    let header = network.header(layer);
+    let contracts = doc_location(network, contracts_table(layer));
 
-    let multicall = Multicall3::new(
-        Address::from_str(MULTICALL3_ADDRESS).context("Invalid Multicall3 constant")?,
-        ProviderBuilder::new().on_http(rpc_url.parse().context("Invalid RPC URL")?),
-    );
+    let provider = connect(network, layer, &rpc_url).await?;
+
+    // Resolve tags like `latest` once, so every multicall round reads the same block
+    let block = provider
+        .get_block(block, BlockTransactionsKind::Hashes)
+        .await
+        .context(format!("Failed to fetch block {} on {}", block, header))?
+        .ok_or_else(|| anyhow!("Block {} not found on {}", block, header))?;
+    let pinned = BlockId::hash(block.header.hash);
+    let multicall = Multicall3::new(network.multicall3(layer), provider.clone());
+
+    let roots = resolve_roots(networks, checks, layer, contracts)?;
+    let mut outcomes: Vec<Option<CheckResult>> = checks.iter().map(|_| None).collect();
+
+    // Checks targeting another check's result wait for it, so each round batches every
+    // check whose target is known into one multicall
+    loop {
+        let round = next_round(checks, layer, network, &roots, &mut outcomes)?;
+        if round.is_empty() {
+            break;
+        }
+
+        let calls = round
+            .iter()
+            .map(|(i, target)| Multicall3::Call3 {
+                target: *target,
+                allowFailure: true,
+                callData: checks[*i].call_data.clone().into(),
+            })
+            .collect();
+
+        let result = multicall
+            .aggregate3(calls)
+            .block(pinned)
+            .call()
+            .await
+            .context(format!("Multicall execution failed on {}", header))?;
+
+        record_round(
+            networks,
+            network,
+            checks,
+            &round,
+            &result.returnData,
+            &mut outcomes,
+        )?;
+    }
+
+    let mut check_results = finish_rounds(checks, layer, network, outcomes);
+
+    match layer {
+        Layer::L1 => {
+            let admin_roles = doc_location(network, DocNetwork::AdminRoles);
+            check_results.extend(
+                verify_key_types(&provider, network.multicall3, pinned, networks, admin_roles)
+                    .await,
+            );
+            check_results.extend(verify_proxy_slots(&provider, pinned, networks, contracts).await);
+            check_results.extend(
+                verify_proxy_admin(&provider, network.multicall3, pinned, networks, contracts)
+                    .await,
+            );
+        }
+        Layer::L2 => {
+            check_results.extend(verify_code_exists(&provider, pinned, networks, contracts).await);
+            check_results.extend(
+                verify_predeploy_implementations(&provider, pinned, network, networks, contracts)
+                    .await,
+            );
+        }
+    }
+
+    Ok(Some(NetworkReport {
+        block_number: block.header.number,
+        block_hash: block.header.hash,
+        results: check_results,
+    }))
+}
+
+/// Docs address of the root of every check on `layer`, `None` for a root missing from
+/// the docs.
+fn resolve_roots<'a>(
+    networks: &[Network],
+    checks: &'a [CheckConfig],
+    layer: Layer,
+    contracts: NetworkRef,
+) -> Result<HashMap<&'a str, Option<Address>>> {
+    let mut roots = HashMap::new();
+    for check in checks.iter().filter(|c| c.layer == layer) {
+        let root = check.root(checks)?;
+        roots
+            .entry(root)
+            .or_insert_with(|| get_addr(networks, contracts, root).ok());
+    }
+    Ok(roots)
+}
+
+/// Picks the checks on `layer` to run in the next multicall round, with the address each
+/// one calls: those whose target, and the check their expected value comes from, are
+/// known. Checks that can no longer run are resolved as skipped or failed instead.
+fn next_round(
+    checks: &[CheckConfig],
+    layer: Layer,
+    network: &NetworkConfig,
+    roots: &HashMap<&str, Option<Address>>,
+    outcomes: &mut [Option<CheckResult>],
+) -> Result<Vec<(usize, Address)>> {
+    // A root missing from the docs only skips the checks that depend on it
+    for (i, check) in checks.iter().enumerate() {
+        if check.layer != layer || outcomes[i].is_some() {
+            continue;
+        }
+        let root = check.root(checks)?;
+        if let Some(None) = roots.get(root) {
+            outcomes[i] = Some(CheckResult {
+                missing_dependency: Some(root.to_string()),
+                ..CheckResult::new(&check.name, doc_location(network, doc_network_of(check)))
+            });
+        }
+    }
 
-    struct CheckConfig<'a> {
-        name: &'a str,
-        file_search_name: &'a str,
-        network: &'a str,
-        call_data: Vec<u8>,
-        target: Address,
-        decoder: Decoder,
-    }
-
-    // Helper to create a decoder
-    fn make_decoder<C: SolCall>(f: fn(C::Return) -> Address) -> Decoder
-    where
-        C::Return: Send + Sync + 'static,
-    {
-        Box::new(move |data| {
-            let ret = C::abi_decode_returns(data, true)?;
-            Ok(f(ret))
+    // Resolving a check may unblock one listed before it, so repeat until a round is
+    // found or nothing changes
+    loop {
+        let mut round = Vec::new();
+        let mut resolved = false;
+
+        for (i, check) in checks.iter().enumerate() {
+            if check.layer != layer || outcomes[i].is_some() {
+                continue;
+            }
+            // A check compared against another check's result waits for it as well
+            if let Expected::Check(other) = &check.expected
+                && outcomes[position_of(checks, other)?].is_none()
+            {
+                continue;
+            }
+            match &check.target {
+                Target::DocRow { doc_row } => {
+                    if let Some(Some(target)) = roots.get(doc_row.as_str()) {
+                        round.push((i, *target));
+                    }
+                }
+                Target::Check { check: dependency } => {
+                    let j = position_of(checks, dependency)?;
+                    let Some(outcome) = &outcomes[j] else {
+                        continue;
+                    };
+                    if let Some(Value::Address(target)) = outcome.actual {
+                        round.push((i, target));
+                        continue;
+                    }
+                    // The dependency never ran, so neither can this check
+                    let missing_dependency = outcome
+                        .actual
+                        .is_none()
+                        .then(|| outcome.missing_dependency.clone())
+                        .flatten();
+                    let error = missing_dependency.is_none().then(|| {
+                        format!("Target check '{}' did not return an address", dependency)
+                    });
+                    outcomes[i] = Some(CheckResult {
+                        error,
+                        missing_dependency,
+                        ..CheckResult::new(
+                            &check.name,
+                            doc_location(network, doc_network_of(check)),
+                        )
+                    });
+                    resolved = true;
+                }
+            }
+        }
+
+        if !round.is_empty() || !resolved {
+            return Ok(round);
+        }
+    }
+}
+
+/// Records the outcome of every check of a round from its multicall return data.
+fn record_round(
+    networks: &[Network],
+    network: &NetworkConfig,
+    checks: &[CheckConfig],
+    round: &[(usize, Address)],
+    returns: &[Multicall3::Result],
+    outcomes: &mut [Option<CheckResult>],
+) -> Result<()> {
+    for ((i, _), res) in round.iter().zip(returns) {
+        let check = &checks[*i];
+        let location = doc_location(network, doc_network_of(check));
+        let expected = match &check.expected {
+            Expected::Check(other) => {
+                let outcome = outcomes[position_of(checks, other)?].as_ref();
+                match outcome.and_then(|r| r.actual.clone()) {
+                    Some(value) => Ok(value),
+                    None => match outcome.and_then(|r| r.missing_dependency.clone()) {
+                        Some(row) => Err(Unresolved::MissingRow(row)),
+                        None => Err(Unresolved::Error(format!(
+                            "Check '{}' returned no value to compare",
+                            other
+                        ))),
+                    },
+                }
+            }
+            Expected::DocRow(row, _)
+                if find_contract_address(networks, location, row).is_none() =>
+            {
+                Err(Unresolved::MissingRow(row.clone()))
+            }
+            expected => {
+                resolve_expected(networks, network, location, expected).map_err(Unresolved::Error)
+            }
+        };
+        let (expected, note) = match expected.map(|v| check.convert_expected(v)) {
+            Ok(Ok((value, note))) => (Ok(value), note),
+            Ok(Err(e)) => (Err(Unresolved::Error(e.to_string())), None),
+            Err(e) => (Err(e), None),
+        };
+        let mut result = process_result(check, location, expected, res);
+        result.expected_note = note;
+        outcomes[*i] = Some(result);
+    }
+    Ok(())
+}
+
+/// Results of the checks on `layer` once no more rounds can run, failing those whose
+/// call target was never resolved.
+fn finish_rounds(
+    checks: &[CheckConfig],
+    layer: Layer,
+    network: &NetworkConfig,
+    outcomes: Vec<Option<CheckResult>>,
+) -> Vec<CheckResult> {
+    outcomes
+        .into_iter()
+        .zip(checks)
+        .filter(|(_, check)| check.layer == layer)
+        .map(|(outcome, check)| {
+            outcome.unwrap_or_else(|| CheckResult {
+                error: Some("Call target could not be resolved".to_string()),
+                ..CheckResult::new(&check.name, doc_location(network, doc_network_of(check)))
+            })
         })
+        .collect()
+}
+
+/// Connects to the RPC of one chain of a registry entry, refusing one that serves a
+/// different chain than the registry expects.
+async fn connect(network: &NetworkConfig, layer: Layer, rpc_url: &str) -> Result<ReqwestProvider> {
+    let provider = ProviderBuilder::new().on_http(rpc_url.parse().context("Invalid RPC URL")?);
+
+    // Catch an RPC for the wrong network before it produces a wall of mismatches
+    let chain_id = provider.get_chain_id().await.context(format!(
+        "Failed to query chain id for {}",
+        network.header(layer)
+    ))?;
+    if chain_id != network.chain_id(layer) {
+        return Err(ChainIdMismatch {
+            name: network.name.clone(),
+            flag: layer.rpc_flag(),
+            rpc_env: network.rpc_env(layer).to_string(),
+            expected: network.chain_id(layer),
+            actual: chain_id,
+        }
+        .into());
     }
 
-    // Common decoder for simple address returns
-    // Note: Most functions generated by alloy for `returns (address)` return a tuple `(Address,)`
-    // or struct with field `_0`.
+    Ok(provider)
+}
 
-    let checks: Vec<CheckConfig> = vec![
-        CheckConfig {
-            name: "Batch Inbox",
-            file_search_name: "Batch Inbox",
-            network: l2_network_name,
-            call_data: SystemConfig::batchInboxCall {}.abi_encode(),
-            target: sys_config,
-            decoder: make_decoder::<SystemConfig::batchInboxCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "DisputeGameFactory",
-            file_search_name: "DisputeGameFactoryProxy",
-            network: l1_network_name,
-            call_data: SystemConfig::disputeGameFactoryCall {}.abi_encode(),
-            target: sys_config,
-            decoder: make_decoder::<SystemConfig::disputeGameFactoryCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "Fault Dispute Game",
-            file_search_name: "FaultDisputeGame",
-            network: l1_network_name,
-            call_data: DisputeGameFactory::gameImplsCall { gameType: 0 }.abi_encode(),
-            target: dispute_game_factory,
-            decoder: make_decoder::<DisputeGameFactory::gameImplsCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "Permissioned Dispute Game",
-            file_search_name: "PermissionedDisputeGame",
-            network: l1_network_name,
-            call_data: DisputeGameFactory::gameImplsCall { gameType: 1 }.abi_encode(),
-            target: dispute_game_factory,
-            decoder: make_decoder::<DisputeGameFactory::gameImplsCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "Challenger",
-            file_search_name: "Challenger",
-            network: l2_network_name,
-            call_data: PermissionedDisputeGame::challengerCall {}.abi_encode(),
-            target: permissioned_dispute_game,
-            decoder: make_decoder::<PermissionedDisputeGame::challengerCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "Proposer",
-            file_search_name: "Output Proposer",
-            network: l2_network_name,
-            call_data: PermissionedDisputeGame::proposerCall {}.abi_encode(),
-            target: permissioned_dispute_game,
-            decoder: make_decoder::<PermissionedDisputeGame::proposerCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "Guardian",
-            file_search_name: "Guardian",
-            network: l2_network_name,
-            call_data: SystemConfig::guardianCall {}.abi_encode(),
-            target: sys_config,
-            decoder: make_decoder::<SystemConfig::guardianCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "L1CrossDomainMessenger",
-            file_search_name: "L1CrossDomainMessenger",
-            network: l1_network_name,
-            call_data: SystemConfig::l1CrossDomainMessengerCall {}.abi_encode(),
-            target: sys_config,
-            decoder: make_decoder::<SystemConfig::l1CrossDomainMessengerCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "L1ERC721Bridge",
-            file_search_name: "L1ERC721Bridge",
-            network: l1_network_name,
-            call_data: SystemConfig::l1ERC721BridgeCall {}.abi_encode(),
-            target: sys_config,
-            decoder: make_decoder::<SystemConfig::l1ERC721BridgeCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "L1StandardBridge",
-            file_search_name: "L1StandardBridge",
-            network: l1_network_name,
-            call_data: SystemConfig::l1StandardBridgeCall {}.abi_encode(),
-            target: sys_config,
-            decoder: make_decoder::<SystemConfig::l1StandardBridgeCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "OptimismMintableERC20Factory",
-            file_search_name: "OptimismMintableERC20Factory",
-            network: l1_network_name,
-            call_data: SystemConfig::optimismMintableERC20FactoryCall {}.abi_encode(),
-            target: sys_config,
-            decoder: make_decoder::<SystemConfig::optimismMintableERC20FactoryCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "OptimismPortal",
-            file_search_name: "OptimismPortal",
-            network: l1_network_name,
-            call_data: SystemConfig::optimismPortalCall {}.abi_encode(),
-            target: sys_config,
-            decoder: make_decoder::<SystemConfig::optimismPortalCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "ProxyAdmin",
-            file_search_name: "ProxyAdmin",
-            network: l1_network_name,
-            call_data: SystemConfig::proxyAdminCall {}.abi_encode(),
-            target: sys_config,
-            decoder: make_decoder::<SystemConfig::proxyAdminCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "Proxy Admin Owner",
-            file_search_name: "Proxy Admin Owner (L1)",
-            network: l2_network_name,
-            call_data: SystemConfig::proxyAdminOwnerCall {}.abi_encode(),
-            target: sys_config,
-            decoder: make_decoder::<SystemConfig::proxyAdminOwnerCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "SystemConfig Owner",
-            file_search_name: "System config owner",
-            network: l2_network_name,
-            call_data: SystemConfig::ownerCall {}.abi_encode(),
-            target: sys_config,
-            decoder: make_decoder::<SystemConfig::ownerCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "AnchorStateRegistry",
-            file_search_name: "AnchorStateRegistryProxy",
-            network: l1_network_name,
-            call_data: FaultDisputeGame::anchorStateRegistryCall {}.abi_encode(),
-            target: fault_dispute_game,
-            decoder: make_decoder::<FaultDisputeGame::anchorStateRegistryCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "MIPS",
-            file_search_name: "MIPS",
-            network: l1_network_name,
-            call_data: FaultDisputeGame::vmCall {}.abi_encode(),
-            target: fault_dispute_game,
-            decoder: make_decoder::<FaultDisputeGame::vmCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "PreimageOracle",
-            file_search_name: "PreimageOracle",
-            network: l1_network_name,
-            call_data: MIPS::oracleCall {}.abi_encode(),
-            target: mips,
-            decoder: make_decoder::<MIPS::oracleCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "DelayedWETHProxy (FDG)",
-            file_search_name: "DelayedWETHProxy (FDG)",
-            network: l1_network_name,
-            call_data: FaultDisputeGame::wethCall {}.abi_encode(),
-            target: fault_dispute_game,
-            decoder: make_decoder::<FaultDisputeGame::wethCall>(|r| r._0),
-        },
-        CheckConfig {
-            name: "DelayedWETHProxy (PDG)",
-            file_search_name: "DelayedWETHProxy (PDG)",
-            network: l1_network_name,
-            call_data: PermissionedDisputeGame::wethCall {}.abi_encode(),
-            target: permissioned_dispute_game,
-            decoder: make_decoder::<PermissionedDisputeGame::wethCall>(|r| r._0),
-        },
-    ];
+/// RPC URL of one chain of a registry entry: the last matching `NAME=URL` override, or
+/// the chain's environment variable.
+fn rpc_url_for(
+    overrides: &[(String, String)],
+    network: &NetworkConfig,
+    layer: Layer,
+) -> Option<String> {
+    overrides
+        .iter()
+        .rev()
+        .find(|(name, _)| name == &network.name)
+        .map(|(_, url)| url.clone())
+        .or_else(|| std::env::var(network.rpc_env(layer)).ok())
+}
 
-    let mut calls = Vec::with_capacity(checks.len());
-    let mut expected_addresses = Vec::with_capacity(checks.len());
+/// Block given for a network with `--block`-style `[NAME=]BLOCK` values, where a
+/// network-specific value wins over one given for all networks.
+fn block_for(blocks: &[(Option<String>, BlockId)], network: &str) -> Option<BlockId> {
+    let mut matching = blocks.iter().rev();
+    matching
+        .clone()
+        .find(|(name, _)| name.as_deref() == Some(network))
+        .or_else(|| matching.find(|(name, _)| name.is_none()))
+        .map(|(_, block)| *block)
+}
 
-    for check in &checks {
-        let expected = find_contract_address(networks, check.network, check.file_search_name);
-        expected_addresses.push(expected);
+/// Docs row whose contract a check calls, following a target check to the row it verifies.
+fn target_doc_row<'a>(checks: &'a [CheckConfig], check: &'a CheckConfig) -> Option<&'a str> {
+    match &check.target {
+        Target::DocRow { doc_row } => Some(doc_row),
+        Target::Check { check } => match &checks.iter().find(|c| &c.name == check)?.expected {
+            Expected::DocRow(row, DocNetwork::L1Contracts) => Some(row),
+            _ => None,
+        },
+    }
+}
 
-        calls.push(Multicall3::Call3 {
-            target: check.target,
-            allowFailure: true,
-            callData: check.call_data.clone().into(),
-        });
+/// Docs table of `network` that rows of `doc_network` are listed in.
+///
+/// L1 contracts and the admin roles (which also live on L1) are listed in separate
+/// sections, the latter under the L2 network's header.
+fn doc_location(network: &NetworkConfig, doc_network: DocNetwork) -> NetworkRef<'_> {
+    match doc_network {
+        DocNetwork::L1Contracts => network_ref(L1_CONTRACTS_SECTION, &network.l1_header),
+        DocNetwork::L2Contracts => network_ref(L2_CONTRACTS_SECTION, &network.l2_header),
+        DocNetwork::AdminRoles => network_ref(ADMIN_SECTION, &network.l2_header),
     }
+}
 
-    let result = multicall
-        .aggregate3(calls)
-        .call()
-        .await
-        .context(format!("Multicall execution failed on {}", l1_network_name))?;
+fn position_of(checks: &[CheckConfig], name: &str) -> Result<usize> {
+    checks
+        .iter()
+        .position(|c| c.name == name)
+        .ok_or_else(|| anyhow!("Unknown check '{}'", name))
+}
 
-    let mut check_results = Vec::new();
+/// Contracts table listing the rows that checks on `layer` call.
+fn contracts_table(layer: Layer) -> DocNetwork {
+    match layer {
+        Layer::L1 => DocNetwork::L1Contracts,
+        Layer::L2 => DocNetwork::L2Contracts,
+    }
+}
 
-    for (i, check) in checks.iter().enumerate() {
-        let res = &result.returnData[i];
+/// Docs table a check's result is reported against.
+fn doc_network_of(check: &CheckConfig) -> DocNetwork {
+    match &check.expected {
+        Expected::DocRow(_, doc_network) => *doc_network,
+        Expected::Literal(_) | Expected::Check(_) | Expected::Derived(_) => {
+            contracts_table(check.layer)
+        }
+    }
+}
 
-        let result = process_result(
-            check.name,
-            check.network,
-            expected_addresses[i].clone(),
-            res,
-            &check.decoder,
-        );
-        check_results.push(result);
+fn parse_rpc_url(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .map(|(name, url)| (name.to_string(), url.to_string()))
+        .ok_or_else(|| format!("expected NAME=URL, got '{}'", s))
+}
+
+fn parse_block(s: &str) -> Result<(Option<String>, BlockId), String> {
+    let (name, block) = match s.split_once('=') {
+        Some((name, block)) => (Some(name.to_string()), block),
+        None => (None, s),
+    };
+    let block =
+        BlockId::from_str(block).map_err(|e| format!("invalid block '{}': {}", block, e))?;
+    // A pending block has no stable hash to pin, and neither tag names a block to verify at
+    if let BlockId::Number(tag @ (BlockNumberOrTag::Pending | BlockNumberOrTag::Earliest)) = block {
+        return Err(format!(
+            "unsupported block '{}', expected a number, hash, latest, safe or finalized",
+            tag
+        ));
     }
+    Ok((name, block))
+}
 
-    Ok(check_results)
+fn network_ref<'a>(section: &'a str, network: &'a str) -> NetworkRef<'a> {
+    NetworkRef { section, network }
 }
 
-fn get_addr(networks: &[Network], network_name: &str, contract_name: &str) -> Result<Address> {
-    let addr_str =
-        find_contract_address(networks, network_name, contract_name).ok_or_else(|| {
-            anyhow!(
-                "Could not find {} address for {}",
-                contract_name,
-                network_name
-            )
-        })?;
+fn get_addr(networks: &[Network], location: NetworkRef, contract_name: &str) -> Result<Address> {
+    let addr_str = find_contract_address(networks, location, contract_name)
+        .ok_or_else(|| anyhow!("Could not find {} address for {}", contract_name, location))?;
 
-    Address::from_str(&addr_str).with_context(|| {
-        format!(
-            "Error parsing {} address for {}",
-            contract_name, network_name
-        )
-    })
+    Address::from_str(&addr_str)
+        .with_context(|| format!("Error parsing {} address for {}", contract_name, location))
 }
 
 fn find_contract_address(
     networks: &[Network],
-    network_name: &str,
+    location: NetworkRef,
     contract_name: &str,
 ) -> Option<String> {
     networks
         .iter()
-        .find(|n| n.name == network_name)
+        .find(|n| n.location() == location)
         .and_then(|n| {
             n.contracts
                 .iter()
@@ -499,63 +1325,418 @@ fn find_contract_address(
         })
 }
 
+/// Resolves a check's expected value, describing why if it cannot be determined.
+fn resolve_expected(
+    networks: &[Network],
+    network: &NetworkConfig,
+    location: NetworkRef,
+    expected: &Expected,
+) -> Result<Value, String> {
+    match expected {
+        Expected::Literal(value) => Ok(value.clone()),
+        Expected::Derived(derivation) => Ok(Value::Address(derivation.derive(network))),
+        Expected::Check(check) => Err(format!(
+            "Expected value is the on-chain result of check '{}'",
+            check
+        )),
+        Expected::DocRow(row, _) => {
+            let expected_str = find_contract_address(networks, location, row).ok_or_else(|| {
+                format!("Could not find expected address in config for {}", location)
+            })?;
+            Address::from_str(&expected_str)
+                .map(Value::Address)
+                .map_err(|e| format!("Error parsing expected address {}: {}", expected_str, e))
+        }
+    }
+}
+
+/// Records the on-chain value of a check, then compares it with the expected value.
+///
+/// The value is recorded even when the expected one is unknown, so checks targeting or
+/// compared against this one can still run.
 fn process_result(
-    contract_name: &str,
-    expected_addr_network: &str,
-    expected_addr: Option<String>,
+    check: &CheckConfig,
+    expected_network: NetworkRef,
+    expected: Result<Value, Unresolved>,
     res: &Multicall3::Result,
-    decoder: &Decoder,
 ) -> CheckResult {
-    let mut result = CheckResult {
-        name: contract_name.to_string(),
-        network: expected_addr_network.to_string(),
-        expected: None,
-        actual: None,
-        success: false,
-        error: None,
-    };
+    let mut result = CheckResult::new(&check.name, expected_network);
 
-    let expected_str = match expected_addr {
-        Some(s) => s,
-        None => {
-            result.error = Some(format!(
-                "Could not find expected address in config for {}",
-                expected_addr_network
-            ));
-            return result;
-        }
-    };
-
-    let expected = match Address::from_str(&expected_str) {
-        Ok(a) => a,
-        Err(e) => {
-            result.error = Some(format!(
-                "Error parsing expected address {}: {}",
-                expected_str, e
-            ));
-            return result;
-        }
-    };
-    result.expected = Some(expected);
+    // A missing docs row skips the comparison whatever the call returned
+    if let Err(Unresolved::MissingRow(row)) = &expected {
+        result.missing_dependency = Some(row.clone());
+    }
 
     if !res.success {
         result.error = Some("View call failed on-chain".to_string());
         return result;
     }
 
-    let on_chain_addr = match decoder(&res.returnData) {
-        Ok(addr) => addr,
+    let on_chain = match check.decode(&res.returnData) {
+        Ok(v) => v,
         Err(e) => {
             result.error = Some(format!("Error decoding return data: {}", e));
             return result;
         }
     };
-    result.actual = Some(on_chain_addr);
+    result.actual = Some(on_chain.clone());
+
+    let expected = match expected {
+        Ok(v) => v,
+        Err(Unresolved::MissingRow(_)) => return result,
+        Err(Unresolved::Error(e)) => {
+            result.error = Some(e);
+            return result;
+        }
+    };
+    result.expected = Some(expected.clone());
 
-    if on_chain_addr != expected {
+    if on_chain.kind() != expected.kind() {
+        result.error = Some(format!(
+            "Type mismatch: expected a {} value, chain returned a {} ({})",
+            expected.kind(),
+            on_chain.kind(),
+            on_chain
+        ));
+        return result;
+    }
+
+    if on_chain != expected {
         return result; // success is already false
     }
 
     result.success = true;
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolValue;
+
+    const CHECKS: &str = r#"
+        [[check]]
+        name = "DisputeGameFactory"
+        doc_row = "DisputeGameFactoryProxy"
+        target = { doc_row = "SystemConfig" }
+        function = "disputeGameFactory()"
+        returns = "address"
+
+        [[check]]
+        name = "Fault Dispute Game"
+        doc_row = "FaultDisputeGame"
+        target = { doc_row = "DisputeGameFactoryProxy" }
+        function = "gameImpls(uint32)"
+        args = ["0"]
+        returns = "address"
+
+        [[check]]
+        name = "Permissioned Dispute Game"
+        doc_row = "PermissionedDisputeGame"
+        target = { doc_row = "DisputeGameFactoryProxy" }
+        function = "gameImpls(uint32)"
+        args = ["1"]
+        returns = "address"
+
+        [[check]]
+        name = "MIPS (FDG)"
+        doc_row = "MIPS"
+        target = { check = "Fault Dispute Game" }
+        function = "vm()"
+        returns = "address"
+
+        [[check]]
+        name = "PreimageOracle (FDG)"
+        doc_row = "PreimageOracle"
+        target = { check = "MIPS (FDG)" }
+        function = "oracle()"
+        returns = "address"
+
+        [[check]]
+        name = "MIPS (PDG matches FDG)"
+        expected_check = "MIPS (FDG)"
+        target = { check = "Permissioned Dispute Game" }
+        function = "vm()"
+        returns = "address"
+
+        [[check]]
+        name = "OptimismPortal.systemConfig() -> SystemConfig"
+        doc_row = "SystemConfig"
+        target = { doc_row = "OptimismPortal" }
+        function = "systemConfig()"
+        returns = "address"
+    "#;
+
+    /// Docs rows, each documented at the address the chain returns for it.
+    const ROWS: &[(&str, u8)] = &[
+        ("SystemConfig", 1),
+        ("DisputeGameFactoryProxy", 2),
+        ("FaultDisputeGame", 3),
+        ("PermissionedDisputeGame", 4),
+        ("MIPS", 5),
+        ("PreimageOracle", 6),
+        ("OptimismPortal", 7),
+    ];
+
+    fn row_address(row: &str) -> Address {
+        let (_, byte) = ROWS.iter().find(|(name, _)| *name == row).unwrap();
+        Address::with_last_byte(*byte)
+    }
+
+    /// Runs every round against a chain that matches the docs, with `missing` left out of
+    /// the L1 contracts table.
+    fn run(missing: &str) -> HashMap<String, CheckResult> {
+        let mut doc = String::from(
+            "## L1 Contract Addresses\n\n### Ethereum Mainnet\n\n| Name | Address |\n| - | - |\n",
+        );
+        for (row, _) in ROWS.iter().filter(|(row, _)| *row != missing) {
+            doc += &format!("| {} | {} |\n", row, row_address(row));
+        }
+        let networks = parse_networks(&doc).unwrap();
+        let checks = load_checks(CHECKS).unwrap();
+        let network = &load_registry(DEFAULT_NETWORKS).unwrap()[0];
+        let contracts = doc_location(network, DocNetwork::L1Contracts);
+
+        let roots = resolve_roots(&networks, &checks, Layer::L1, contracts).unwrap();
+        let mut outcomes: Vec<Option<CheckResult>> = checks.iter().map(|_| None).collect();
+        loop {
+            let round = next_round(&checks, Layer::L1, network, &roots, &mut outcomes).unwrap();
+            if round.is_empty() {
+                break;
+            }
+            let returns: Vec<Multicall3::Result> = round
+                .iter()
+                .map(|(i, _)| {
+                    let row = match &checks[*i].expected {
+                        Expected::DocRow(row, _) => row.as_str(),
+                        _ => "MIPS",
+                    };
+                    Multicall3::Result {
+                        success: true,
+                        returnData: row_address(row).abi_encode().into(),
+                    }
+                })
+                .collect();
+            record_round(&networks, network, &checks, &round, &returns, &mut outcomes).unwrap();
+        }
+
+        finish_rounds(&checks, Layer::L1, network, outcomes)
+            .into_iter()
+            .map(|r| (r.name.clone(), r))
+            .collect()
+    }
+
+    fn skipped(results: &HashMap<String, CheckResult>) -> Vec<(&str, &str)> {
+        let mut skipped: Vec<(&str, &str)> = results
+            .values()
+            .filter_map(|r| Some((r.name.as_str(), r.missing_dependency.as_deref()?)))
+            .collect();
+        skipped.sort();
+        skipped
+    }
+
+    #[test]
+    fn complete_docs_pass_every_check() {
+        let results = run("");
+        assert_eq!(results.len(), 7);
+        assert!(results.values().all(|r| r.success), "{:?}", results);
+    }
+
+    #[test]
+    fn missing_row_skips_only_its_dependents() {
+        let results = run("MIPS");
+
+        // The MIPS check still passes its on-chain result on to the checks built on it
+        assert_eq!(skipped(&results), [("MIPS (FDG)", "MIPS")]);
+        assert_eq!(
+            results["MIPS (FDG)"].actual,
+            Some(Value::Address(row_address("MIPS")))
+        );
+        for name in ["PreimageOracle (FDG)", "MIPS (PDG matches FDG)"] {
+            assert!(results[name].success, "{}", name);
+        }
+    }
+
+    #[test]
+    fn missing_root_skips_only_the_checks_rooted_there() {
+        let results = run("SystemConfig");
+
+        assert_eq!(
+            skipped(&results),
+            [
+                ("DisputeGameFactory", "SystemConfig"),
+                (
+                    "OptimismPortal.systemConfig() -> SystemConfig",
+                    "SystemConfig"
+                ),
+            ]
+        );
+        for name in [
+            "Fault Dispute Game",
+            "Permissioned Dispute Game",
+            "MIPS (FDG)",
+            "PreimageOracle (FDG)",
+            "MIPS (PDG matches FDG)",
+        ] {
+            assert!(results[name].success, "{}", name);
+        }
+    }
+
+    #[test]
+    fn missing_target_row_skips_checks_through_target_checks() {
+        let results = run("DisputeGameFactoryProxy");
+
+        assert_eq!(
+            skipped(&results),
+            [
+                ("DisputeGameFactory", "DisputeGameFactoryProxy"),
+                ("Fault Dispute Game", "DisputeGameFactoryProxy"),
+                ("MIPS (FDG)", "DisputeGameFactoryProxy"),
+                ("MIPS (PDG matches FDG)", "DisputeGameFactoryProxy"),
+                ("Permissioned Dispute Game", "DisputeGameFactoryProxy"),
+                ("PreimageOracle (FDG)", "DisputeGameFactoryProxy"),
+            ]
+        );
+        assert!(results["OptimismPortal.systemConfig() -> SystemConfig"].success);
+    }
+
+    #[test]
+    fn flags_proxy_admin_ownership_moving_away_from_the_documented_owner() {
+        let proxy_admin = Address::with_last_byte(0x18);
+        let system_config = Address::with_last_byte(1);
+        let owner = Address::with_last_byte(0xaa);
+        let doc = format!(
+            "## L1 Contract Addresses\n\n### Ethereum Mainnet\n\n| Name | Address |\n| - | - |\n\
+             | ProxyAdmin | {} |\n| SystemConfig | {} |\n\n\
+             ## Base Admin Addresses\n\n### Base Mainnet\n\n| Name | Address |\n| - | - |\n\
+             | Proxy Admin Owner (L1) | {} |\n| System config owner | {} |\n",
+            proxy_admin, system_config, owner, owner
+        );
+        let networks = parse_networks(&doc).unwrap();
+        let checks = load_checks(DEFAULT_CHECKS).unwrap();
+        let network = &load_registry(DEFAULT_NETWORKS).unwrap()[0];
+        let transferred = |contract, block, new| ChangeEvent {
+            contract,
+            block,
+            timestamp: block * 12,
+            tx: None,
+            change: Change::OwnershipTransferred {
+                previous: Address::ZERO,
+                new,
+            },
+        };
+        let documented =
+            |event: &ChangeEvent| documented_change(&networks, &checks, network, event);
+
+        let events = [
+            transferred(proxy_admin, 1, owner),
+            transferred(proxy_admin, 2, Address::with_last_byte(0xbb)),
+        ];
+        assert_eq!(
+            documented(&events[1]),
+            Some((PROXY_ADMIN_OWNER_ROW.to_string(), owner))
+        );
+        assert_eq!(
+            after_docs(&events, |e| documented(e).map(|(_, address)| address)),
+            [false, true]
+        );
+
+        // SystemConfig ownership is still compared through the check reading it
+        assert_eq!(
+            documented(&transferred(system_config, 3, owner)),
+            Some(("SystemConfig Owner".to_string(), owner))
+        );
+    }
+
+    #[test]
+    fn parses_block_numbers_hashes_and_tags() {
+        let hash = "0x3c8e1a6cdb2e9d7f5b4a3c2d1e0f9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f";
+        for (arg, expected) in [
+            ("20000000", (None, BlockId::number(20_000_000))),
+            ("0x10", (None, BlockId::number(16))),
+            ("latest", (None, BlockId::latest())),
+            ("safe", (None, BlockId::safe())),
+            ("finalized", (None, BlockId::finalized())),
+            (
+                "sepolia=finalized",
+                (Some("sepolia".to_string()), BlockId::finalized()),
+            ),
+            (hash, (None, BlockId::hash(B256::from_str(hash).unwrap()))),
+        ] {
+            assert_eq!(parse_block(arg).unwrap(), expected, "{}", arg);
+        }
+    }
+
+    #[test]
+    fn rejects_blocks_without_a_stable_hash() {
+        for arg in ["pending", "earliest", "mainnet=pending", "soon"] {
+            assert!(parse_block(arg).is_err(), "{}", arg);
+        }
+    }
+
+    #[test]
+    fn network_block_wins_over_the_global_one() {
+        let blocks = [
+            parse_block("mainnet=100").unwrap(),
+            parse_block("200").unwrap(),
+        ];
+        assert_eq!(block_for(&blocks, "mainnet"), Some(BlockId::number(100)));
+        assert_eq!(block_for(&blocks, "sepolia"), Some(BlockId::number(200)));
+        assert_eq!(block_for(&blocks[..1], "sepolia"), None);
+
+        // The last value given for the same scope wins
+        let blocks = [parse_block("1").unwrap(), parse_block("2").unwrap()];
+        assert_eq!(block_for(&blocks, "mainnet"), Some(BlockId::number(2)));
+    }
+
+    #[test]
+    fn missing_row_is_a_skip_even_when_the_call_fails() {
+        let checks = load_checks(CHECKS).unwrap();
+        let network = &load_registry(DEFAULT_NETWORKS).unwrap()[0];
+        let location = doc_location(network, DocNetwork::L1Contracts);
+        let failed = Multicall3::Result {
+            success: false,
+            returnData: Default::default(),
+        };
+
+        let result = process_result(
+            &checks[0],
+            location,
+            Err(Unresolved::MissingRow(
+                "DisputeGameFactoryProxy".to_string(),
+            )),
+            &failed,
+        );
+        assert!(!result.success);
+        assert_eq!(
+            result.missing_dependency.as_deref(),
+            Some("DisputeGameFactoryProxy")
+        );
+
+        let result = process_result(
+            &checks[0],
+            location,
+            Ok(Value::Address(Address::ZERO)),
+            &failed,
+        );
+        assert_eq!(result.missing_dependency, None);
+        assert_eq!(result.error.as_deref(), Some("View call failed on-chain"));
+    }
+
+    #[test]
+    fn unresolved_targets_fail_with_the_reason() {
+        let checks = load_checks(CHECKS).unwrap();
+        let network = &load_registry(DEFAULT_NETWORKS).unwrap()[0];
+        let outcomes = checks.iter().map(|_| None).collect();
+
+        let results = finish_rounds(&checks, Layer::L1, network, outcomes);
+        assert_eq!(results.len(), checks.len());
+        for result in &results {
+            assert!(!result.success);
+            assert_eq!(
+                result.error.as_deref(),
+                Some("Call target could not be resolved")
+            );
+        }
+    }
+}
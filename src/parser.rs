@@ -0,0 +1,368 @@
+use anyhow::{Result, anyhow};
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct Cell {
+    /// Header text of the column this cell belongs to
+    pub header: String,
+    /// Rendered text of the cell, with inline markup stripped
+    pub text: String,
+    /// Destination of the first link in the cell, if any
+    pub link: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct Contract {
+    pub name: String,
+    pub address: String,
+    /// 1-based line of the table row in the source file
+    pub line: usize,
+    /// Every cell of the row, in column order
+    pub cells: Vec<Cell>,
+}
+
+impl Contract {
+    /// Cell under the given column header, compared case-insensitively.
+    pub fn column(&self, header: &str) -> Option<&Cell> {
+        self.cells
+            .iter()
+            .find(|c| c.header.eq_ignore_ascii_case(header))
+    }
+
+    /// Link target of the address cell, e.g. the explorer page for the address.
+    pub fn address_link(&self) -> Option<&str> {
+        self.cells.get(1).and_then(|c| c.link.as_deref())
+    }
+}
+
+#[derive(Debug)]
+pub struct Network {
+    /// Text of the enclosing `##` header, empty if the network precedes any section
+    pub section: String,
+    pub name: String,
+    pub contracts: Vec<Contract>,
+}
+
+impl Network {
+    pub fn location(&self) -> NetworkRef<'_> {
+        NetworkRef {
+            section: &self.section,
+            network: &self.name,
+        }
+    }
+}
+
+/// Identifies one network table in the document by its `##` section and `###` header.
+///
+/// The same network header can appear under several sections (e.g. "Base Mainnet" under
+/// both the L2 contracts and the admin roles), so the header alone is not enough.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkRef<'a> {
+    pub section: &'a str,
+    pub network: &'a str,
+}
+
+impl fmt::Display for NetworkRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.section.is_empty() {
+            write!(f, "{}", self.network)
+        } else {
+            write!(f, "{} / {}", self.section, self.network)
+        }
+    }
+}
+
+/// Table currently being walked, along with the rows and cell under construction.
+#[derive(Default)]
+struct TableState {
+    headers: Vec<String>,
+    /// Finished body rows and their lines, kept until the table is known to hold addresses
+    rows: Vec<(Vec<Cell>, usize)>,
+    row: Vec<Cell>,
+    row_line: usize,
+    cell: Option<Cell>,
+}
+
+impl TableState {
+    /// Whether this is an address table, i.e. some body row has an address in its second
+    /// column. Other tables, such as parameter lists, are not contract tables.
+    fn holds_addresses(&self) -> bool {
+        self.rows
+            .iter()
+            .any(|(cells, _)| cells.get(1).is_some_and(|c| is_address_literal(&c.text)))
+    }
+}
+
+/// Parses the Markdown/MDX document into networks and their contract tables.
+///
+/// `##` headers open a section, `###` headers open a network within it and every row of
+/// an address table under a network header becomes a `Contract`. Tables without an
+/// address in their second column are skipped wherever they appear, and tables inside
+/// code blocks are never seen as tables. A malformed row in an address table, or an
+/// address table before any network header, is an error.
+pub fn parse_networks(content: &str) -> Result<Vec<Network>> {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS;
+    let parser = Parser::new_ext(content, options).into_offset_iter();
+
+    // Byte offsets where each line starts, used to report 1-based line numbers
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let line_of = |offset: usize| line_starts.partition_point(|&start| start <= offset);
+
+    let mut networks: Vec<Network> = Vec::new();
+    let mut current_section = String::new();
+    let mut current_network_name: Option<String> = None;
+    let mut heading: Option<(HeadingLevel, String)> = None;
+    let mut table: Option<TableState> = None;
+
+    for (event, range) in parser {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                heading = Some((level, String::new()));
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                let Some((level, text)) = heading.take() else {
+                    continue;
+                };
+                match level {
+                    // A new section closes whichever network the previous one was in
+                    HeadingLevel::H2 => {
+                        current_section = text.trim().to_string();
+                        current_network_name = None;
+                    }
+                    HeadingLevel::H3 => {
+                        let network_name = text.trim().to_string();
+                        if network_name.is_empty() {
+                            return Err(anyhow!(
+                                "Failed to capture network name on line {}",
+                                line_of(range.start)
+                            ));
+                        }
+                        current_network_name = Some(network_name);
+                    }
+                    _ => {}
+                }
+            }
+            Event::Start(Tag::Table(_)) => table = Some(TableState::default()),
+            Event::End(TagEnd::Table) => {
+                let Some(t) = table.take() else {
+                    continue;
+                };
+                if !t.holds_addresses() {
+                    continue;
+                }
+
+                for (cells, line) in t.rows {
+                    let contract = parse_row(cells, line)?;
+
+                    let net_name = current_network_name.clone().ok_or_else(|| {
+                        anyhow!(
+                            "Found contract definition before network header on line {}",
+                            contract.line
+                        )
+                    })?;
+
+                    // Find existing network group within this section or create new one
+                    if let Some(pos) = networks
+                        .iter()
+                        .position(|n| n.section == current_section && n.name == net_name)
+                    {
+                        networks[pos].contracts.push(contract);
+                    } else {
+                        networks.push(Network {
+                            section: current_section.clone(),
+                            name: net_name,
+                            contracts: vec![contract],
+                        });
+                    }
+                }
+            }
+            Event::End(TagEnd::TableHead) => {
+                if let Some(t) = table.as_mut() {
+                    t.headers = t.row.drain(..).map(|c| c.text).collect();
+                }
+            }
+            Event::Start(Tag::TableRow) => {
+                if let Some(t) = table.as_mut() {
+                    t.row.clear();
+                    t.row_line = line_of(range.start);
+                }
+            }
+            Event::Start(Tag::TableCell) => {
+                if let Some(t) = table.as_mut() {
+                    let header = t.headers.get(t.row.len()).cloned().unwrap_or_default();
+                    t.cell = Some(Cell {
+                        header,
+                        text: String::new(),
+                        link: None,
+                    });
+                }
+            }
+            Event::End(TagEnd::TableCell) => {
+                if let Some(t) = table.as_mut()
+                    && let Some(mut cell) = t.cell.take()
+                {
+                    cell.text = cell.text.trim().to_string();
+                    t.row.push(cell);
+                }
+            }
+            Event::End(TagEnd::TableRow) => {
+                if let Some(t) = table.as_mut() {
+                    let cells = std::mem::take(&mut t.row);
+                    t.rows.push((cells, t.row_line));
+                }
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                if let Some(cell) = table.as_mut().and_then(|t| t.cell.as_mut())
+                    && cell.link.is_none()
+                {
+                    cell.link = Some(dest_url.to_string());
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(cell) = table.as_mut().and_then(|t| t.cell.as_mut()) {
+                    cell.text.push_str(&text);
+                } else if let Some((_, heading_text)) = heading.as_mut() {
+                    heading_text.push_str(&text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(networks)
+}
+
+/// Turns the cells of a body row into a `Contract`, expecting the name in the first
+/// column and the address in the second.
+fn parse_row(cells: Vec<Cell>, line: usize) -> Result<Contract> {
+    let name = cells
+        .first()
+        .map(|c| c.text.clone())
+        .filter(|n| !n.is_empty())
+        .ok_or_else(|| anyhow!("Failed to capture contract name on line {}", line))?;
+
+    let address_cell = cells
+        .get(1)
+        .ok_or_else(|| anyhow!("Missing address column on line {}", line))?;
+    if !is_address_literal(&address_cell.text) {
+        return Err(anyhow!(
+            "Failed to capture contract address from '{}' column on line {}",
+            address_cell.header,
+            line
+        ));
+    }
+    let address = address_cell.text.clone();
+
+    Ok(Contract {
+        name,
+        address,
+        line,
+        cells,
+    })
+}
+
+fn is_address_literal(s: &str) -> bool {
+    s.len() == 42 && s.starts_with("0x") && s[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "0x4200000000000000000000000000000000000007";
+
+    fn row(name: &str) -> String {
+        format!(
+            "| {} | [{}](https://example.com/address/{}) |",
+            name, ADDRESS, ADDRESS
+        )
+    }
+
+    #[test]
+    fn parses_rows_with_line_numbers_and_links() {
+        let doc = format!(
+            "## L2\n\n### Base\n\n| Name | Address |\n| :--- | :--- |\n{}\n",
+            row("L2CrossDomainMessenger")
+        );
+        let networks = parse_networks(&doc).unwrap();
+
+        assert_eq!(networks.len(), 1);
+        assert_eq!(networks[0].section, "L2");
+        assert_eq!(networks[0].name, "Base");
+        let contract = &networks[0].contracts[0];
+        assert_eq!(contract.name, "L2CrossDomainMessenger");
+        assert_eq!(contract.address, ADDRESS);
+        assert_eq!(contract.line, 7);
+        assert_eq!(
+            contract.address_link(),
+            Some(format!("https://example.com/address/{}", ADDRESS).as_str())
+        );
+        assert_eq!(contract.column("address").unwrap().text, ADDRESS);
+    }
+
+    #[test]
+    fn keeps_same_network_header_apart_per_section() {
+        let doc = format!(
+            "## L2\n\n### Base\n\n| Name | Address |\n| - | - |\n{}\n\n\
+             ## Admin\n\n### Base\n\n| Name | Address |\n| - | - |\n{}\n{}\n",
+            row("A"),
+            row("B"),
+            row("C")
+        );
+        let networks = parse_networks(&doc).unwrap();
+
+        assert_eq!(networks.len(), 2);
+        assert_eq!(
+            networks[0].location(),
+            NetworkRef {
+                section: "L2",
+                network: "Base"
+            }
+        );
+        assert_eq!(networks[0].contracts.len(), 1);
+        assert_eq!(
+            networks[1].location(),
+            NetworkRef {
+                section: "Admin",
+                network: "Base"
+            }
+        );
+        assert_eq!(networks[1].contracts.len(), 2);
+    }
+
+    #[test]
+    fn ignores_tables_in_code_blocks() {
+        let doc = format!(
+            "### Base\n\n```md\n| Name | Address |\n| - | - |\n{}\n```\n",
+            row("A")
+        );
+        assert!(parse_networks(&doc).unwrap().is_empty());
+    }
+
+    #[test]
+    fn skips_tables_without_addresses() {
+        let doc = "# Title\n\n| a | b |\n| - | - |\n| 1 | 2 |\n\n\
+                   ### Ethereum Mainnet\n\n| Param | Value |\n| - | - |\n| Gas limit | 30000000 |\n";
+        assert!(parse_networks(doc).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_row_in_address_table() {
+        let doc = format!(
+            "### Base\n\n| Name | Address |\n| - | - |\n{}\n| B | TBD |\n",
+            row("A")
+        );
+        let err = parse_networks(&doc).unwrap_err().to_string();
+        assert!(err.contains("line 6"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_address_table_before_network_header() {
+        let doc = format!("| Name | Address |\n| - | - |\n{}\n", row("A"));
+        let err = parse_networks(&doc).unwrap_err().to_string();
+        assert!(err.contains("before network header"), "{}", err);
+    }
+}
@@ -0,0 +1,617 @@
+use alloy::eips::BlockId;
+use alloy::primitives::{Address, address};
+use alloy::providers::{Provider, ReqwestProvider};
+use std::str::FromStr;
+
+use crate::CheckResult;
+use crate::checksum::Severity;
+use crate::constants::{
+    EIP1967_IMPLEMENTATION_SLOT, L2_CONTRACTS_SECTION, PREDEPLOY_IMPLEMENTATION_NAMESPACE,
+};
+use crate::parser::{Network, NetworkRef};
+use crate::registry::NetworkConfig;
+use crate::value::Value;
+
+/// An OP Stack predeploy, deployed at the same L2 address on every chain.
+pub struct Predeploy {
+    pub name: &'static str,
+    /// Other names the docs may list it under
+    pub aliases: &'static [&'static str],
+    pub address: Address,
+    /// Whether the docs are expected to list it; legacy, optional and recent hard fork
+    /// predeploys are not
+    pub required: bool,
+    /// Whether it is a proxy with its implementation in the 0xc0d3… namespace
+    pub proxied: bool,
+    /// Whether hard forks deploy its new implementations outside the 0xc0d3… namespace, so
+    /// registry entries need to pin them
+    pub forked: bool,
+}
+
+/// Canonical OP Stack predeploys, as defined by the protocol.
+pub const PREDEPLOYS: &[Predeploy] = &[
+    Predeploy {
+        name: "LegacyMessagePasser",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000000"),
+        required: false,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "L1MessageSender",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000001"),
+        required: false,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "DeployerWhitelist",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000002"),
+        required: false,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "WETH9",
+        aliases: &["WETH"],
+        address: address!("4200000000000000000000000000000000000006"),
+        required: true,
+        proxied: false,
+        forked: false,
+    },
+    Predeploy {
+        name: "L2CrossDomainMessenger",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000007"),
+        required: true,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "GasPriceOracle",
+        aliases: &[],
+        address: address!("420000000000000000000000000000000000000F"),
+        required: true,
+        proxied: true,
+        forked: true,
+    },
+    Predeploy {
+        name: "L2StandardBridge",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000010"),
+        required: true,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "SequencerFeeVault",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000011"),
+        required: true,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "OptimismMintableERC20Factory",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000012"),
+        required: true,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "L1BlockNumber",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000013"),
+        required: false,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "L2ERC721Bridge",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000014"),
+        required: true,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "L1Block",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000015"),
+        required: true,
+        proxied: true,
+        forked: true,
+    },
+    Predeploy {
+        name: "L2ToL1MessagePasser",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000016"),
+        required: true,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "OptimismMintableERC721Factory",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000017"),
+        required: true,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "ProxyAdmin",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000018"),
+        required: true,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "BaseFeeVault",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000019"),
+        required: true,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "L1FeeVault",
+        aliases: &[],
+        address: address!("420000000000000000000000000000000000001a"),
+        required: true,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "OperatorFeeVault",
+        aliases: &[],
+        address: address!("420000000000000000000000000000000000001b"),
+        required: false,
+        proxied: true,
+        forked: true,
+    },
+    Predeploy {
+        name: "SchemaRegistry",
+        aliases: &["EASSchemaRegistry"],
+        address: address!("4200000000000000000000000000000000000020"),
+        required: true,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "EAS",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000021"),
+        required: true,
+        proxied: true,
+        forked: false,
+    },
+    Predeploy {
+        name: "GovernanceToken",
+        aliases: &[],
+        address: address!("4200000000000000000000000000000000000042"),
+        required: false,
+        proxied: false,
+        forked: false,
+    },
+    Predeploy {
+        name: "LegacyERC20ETH",
+        aliases: &[],
+        address: address!("DeadDeAddeAddEAddeadDEaDDEAdDeaDDeAD0000"),
+        required: true,
+        proxied: false,
+        forked: false,
+    },
+];
+
+impl Predeploy {
+    /// Address its implementation is deployed at, e.g. `0xc0d3…0007` for `0x4200…0007`.
+    pub fn implementation(&self) -> Address {
+        let mut implementation = PREDEPLOY_IMPLEMENTATION_NAMESPACE;
+        implementation[18..].copy_from_slice(&self.address[18..]);
+        implementation
+    }
+
+    /// Implementations it may use on `network`: its 0xc0d3… namespace address, followed by
+    /// those the registry entry pins.
+    pub fn accepted_implementations(&self, network: &NetworkConfig) -> Vec<Address> {
+        let pinned = network
+            .predeploy_implementations
+            .iter()
+            .filter(|(name, _)| self.is_named(name))
+            .flat_map(|(_, addresses)| addresses);
+        std::iter::once(self.implementation())
+            .chain(pinned.copied())
+            .collect()
+    }
+
+    fn is_named(&self, name: &str) -> bool {
+        std::iter::once(&self.name)
+            .chain(self.aliases)
+            .any(|n| n.eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+#[derive(Debug)]
+pub struct PredeployIssue {
+    pub name: String,
+    pub location: String,
+    /// Line of the offending row, `None` for a predeploy missing from the docs
+    pub line: Option<usize>,
+    pub message: String,
+    pub severity: Severity,
+}
+
+/// Compares every L2 contracts table against the canonical predeploys, without any RPC
+/// access.
+///
+/// A row naming a predeploy at another address is an error, unless the registry lists
+/// that address under `non_canonical_predeploys`. Rows naming no predeploy and required
+/// predeploys the table does not list are warnings, since the docs may list
+/// non-predeploy contracts or leave some out on purpose.
+pub fn check_predeploys(networks: &[Network], registry: &[NetworkConfig]) -> Vec<PredeployIssue> {
+    let mut issues = Vec::new();
+
+    for config in registry {
+        let location = NetworkRef {
+            section: L2_CONTRACTS_SECTION,
+            network: &config.l2_header,
+        };
+        let contracts: Vec<_> = networks
+            .iter()
+            .filter(|n| n.location() == location)
+            .flat_map(|n| &n.contracts)
+            .collect();
+        if contracts.is_empty() {
+            continue;
+        }
+
+        for contract in &contracts {
+            let issue = |message: String, severity| PredeployIssue {
+                name: contract.name.clone(),
+                location: location.to_string(),
+                line: Some(contract.line),
+                message,
+                severity,
+            };
+            match PREDEPLOYS.iter().find(|p| p.is_named(&contract.name)) {
+                None => issues.push(issue(
+                    "not a known predeploy".to_string(),
+                    Severity::Warning,
+                )),
+                Some(predeploy) => {
+                    let documented = Address::from_str(&contract.address).ok();
+                    let allowed = config
+                        .non_canonical_predeploys
+                        .iter()
+                        .any(|(name, address)| {
+                            predeploy.is_named(name) && documented == Some(*address)
+                        });
+                    if documented != Some(predeploy.address) && !allowed {
+                        issues.push(issue(
+                            format!(
+                                "documented {}, but {} is predeployed at {}",
+                                contract.address, predeploy.name, predeploy.address
+                            ),
+                            Severity::Error,
+                        ));
+                    }
+                }
+            }
+        }
+
+        for predeploy in PREDEPLOYS.iter().filter(|p| p.required) {
+            if !contracts.iter().any(|c| predeploy.is_named(&c.name)) {
+                issues.push(PredeployIssue {
+                    name: predeploy.name.to_string(),
+                    location: location.to_string(),
+                    line: None,
+                    message: format!(
+                        "predeploy at {} is missing from the docs",
+                        predeploy.address
+                    ),
+                    severity: Severity::Warning,
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// Checks that every row of `location` has code deployed at its documented address. A row
+/// whose code cannot be fetched fails on its own.
+pub async fn verify_code_exists(
+    provider: &ReqwestProvider,
+    block: BlockId,
+    networks: &[Network],
+    location: NetworkRef<'_>,
+) -> Vec<CheckResult> {
+    let mut results = Vec::new();
+
+    for contract in networks
+        .iter()
+        .filter(|n| n.location() == location)
+        .flat_map(|n| &n.contracts)
+    {
+        let result = CheckResult::new(format!("{} (code)", contract.name), location);
+        let address = match Address::from_str(&contract.address) {
+            Ok(address) => address,
+            Err(e) => {
+                results.push(CheckResult {
+                    error: Some(format!("Error parsing address: {}", e)),
+                    ..result
+                });
+                continue;
+            }
+        };
+        let code = match provider.get_code_at(address).block_id(block).await {
+            Ok(code) => code,
+            Err(e) => {
+                results.push(CheckResult {
+                    error: Some(format!("Failed to fetch code: {}", e)),
+                    ..result
+                });
+                continue;
+            }
+        };
+
+        results.push(CheckResult {
+            success: !code.is_empty(),
+            error: code.is_empty().then(|| format!("No code at {}", address)),
+            ..result
+        });
+    }
+
+    results
+}
+
+/// Reads the EIP-1967 implementation slot of every proxied predeploy listed at `location`.
+///
+/// The implementation must be the predeploy's address in the 0xc0d3… namespace or one the
+/// registry entry pins, and have code; anything else points to an upgrade the docs do not
+/// mention. Rows are read at the canonical predeploy address, since a differing docs
+/// address is reported offline, and a row whose state cannot be read fails on its own.
+pub async fn verify_predeploy_implementations(
+    provider: &ReqwestProvider,
+    block: BlockId,
+    network: &NetworkConfig,
+    networks: &[Network],
+    location: NetworkRef<'_>,
+) -> Vec<CheckResult> {
+    let mut results = Vec::new();
+
+    for contract in networks
+        .iter()
+        .filter(|n| n.location() == location)
+        .flat_map(|n| &n.contracts)
+    {
+        let Some(predeploy) = PREDEPLOYS
+            .iter()
+            .find(|p| p.proxied && p.is_named(&contract.name))
+        else {
+            continue;
+        };
+
+        let accepted = predeploy.accepted_implementations(network);
+        let note = match &accepted[1..] {
+            [] if predeploy.forked => Some(
+                "hard forks deploy its implementations outside this namespace; pin them under \
+                 predeploy_implementations"
+                    .to_string(),
+            ),
+            [] => None,
+            pinned => Some(format!(
+                "or a pinned implementation: {}",
+                pinned
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        };
+        let mut result = CheckResult {
+            expected: Some(Value::Address(accepted[0])),
+            expected_note: note,
+            ..CheckResult::new(
+                format!("{} (predeploy implementation)", contract.name),
+                location,
+            )
+        };
+
+        let implementation = async {
+            let word = provider
+                .get_storage_at(predeploy.address, EIP1967_IMPLEMENTATION_SLOT.into())
+                .block_id(block)
+                .await
+                .map_err(|e| format!("Failed to read storage: {}", e))?;
+            let actual = Address::from_word(word.into());
+            let code = provider
+                .get_code_at(actual)
+                .block_id(block)
+                .await
+                .map_err(|e| format!("Failed to fetch code of implementation {}: {}", actual, e))?;
+            Ok::<_, String>((actual, !code.is_empty()))
+        };
+        match implementation.await {
+            Err(e) => result.error = Some(e),
+            Ok((actual, has_code)) => {
+                result.actual = Some(Value::Address(actual));
+                if accepted.contains(&actual) {
+                    result.expected = Some(Value::Address(actual));
+                    result.expected_note = None;
+                }
+                result.success = accepted.contains(&actual) && has_code;
+                result.error = (!has_code).then(|| format!("No code at implementation {}", actual));
+            }
+        }
+        results.push(result);
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_networks;
+    use crate::registry::{DEFAULT_NETWORKS, load_registry};
+
+    /// Issues for an L2 table listing every required predeploy, with `rows` replacing or
+    /// adding to the canonical ones.
+    fn issues(rows: &[(&str, &str)]) -> Vec<PredeployIssue> {
+        let mut table = String::from(
+            "## L2 Contract Addresses\n\n### Base Mainnet\n\n| Name | Address |\n| - | - |\n",
+        );
+        for predeploy in PREDEPLOYS.iter().filter(|p| p.required) {
+            if !rows.iter().any(|(name, _)| predeploy.is_named(name)) {
+                table += &format!("| {} | {} |\n", predeploy.name, predeploy.address);
+            }
+        }
+        for (name, address) in rows {
+            table += &format!("| {} | {} |\n", name, address);
+        }
+        let networks = parse_networks(&table).unwrap();
+        check_predeploys(&networks, &load_registry(DEFAULT_NETWORKS).unwrap())
+    }
+
+    #[test]
+    fn canonical_table_passes() {
+        assert!(issues(&[]).is_empty());
+    }
+
+    #[test]
+    fn accepts_aliases_case_insensitively() {
+        assert!(
+            issues(&[(
+                "easschemaregistry",
+                "0x4200000000000000000000000000000000000020"
+            )])
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn wrong_address_is_an_error() {
+        let issues = issues(&[("L1Block", "0x4200000000000000000000000000000000000016")]);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].name, "L1Block");
+        assert_eq!(issues[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn registry_allows_documented_non_canonical_deployments() {
+        let allowed = issues(&[(
+            "OptimismMintableERC20Factory",
+            "0xF10122D428B4bc8A9d050D06a2037259b4c4B83B",
+        )]);
+        assert!(allowed.is_empty());
+
+        let other = issues(&[(
+            "OptimismMintableERC20Factory",
+            "0x0000000000000000000000000000000000000001",
+        )]);
+        assert_eq!(other[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn unknown_and_missing_rows_are_warnings() {
+        let mut table = String::from(
+            "## L2 Contract Addresses\n\n### Base Mainnet\n\n| Name | Address |\n| - | - |\n",
+        );
+        table += "| Multicall3 | 0xcA11bde05977b3631167028862bE2a173976CA11 |\n";
+        let networks = parse_networks(&table).unwrap();
+        let issues = check_predeploys(&networks, &load_registry(DEFAULT_NETWORKS).unwrap());
+
+        let required = PREDEPLOYS.iter().filter(|p| p.required).count();
+        assert_eq!(issues.len(), required + 1);
+        assert!(issues.iter().all(|i| i.severity == Severity::Warning));
+        assert_eq!(issues[0].name, "Multicall3");
+        assert!(issues[1..].iter().all(|i| i.line.is_none()));
+    }
+
+    #[test]
+    fn registry_pins_implementations_outside_the_namespace() {
+        let mut mainnet = load_registry(DEFAULT_NETWORKS).unwrap().remove(0);
+        let named = |name| PREDEPLOYS.iter().find(|p| p.name == name).unwrap();
+        let upgraded = address!("1111111111111111111111111111111111111111");
+        mainnet
+            .predeploy_implementations
+            .insert("l2crossdomainmessenger".to_string(), vec![upgraded]);
+
+        assert_eq!(
+            named("L2CrossDomainMessenger").accepted_implementations(&mainnet),
+            [named("L2CrossDomainMessenger").implementation(), upgraded]
+        );
+        assert_eq!(
+            named("L2ERC721Bridge").accepted_implementations(&mainnet),
+            [named("L2ERC721Bridge").implementation()]
+        );
+    }
+
+    #[test]
+    fn built_in_registry_pins_the_hard_fork_implementations() {
+        let forked: Vec<_> = PREDEPLOYS
+            .iter()
+            .filter(|p| p.forked)
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(forked, ["GasPriceOracle", "L1Block", "OperatorFeeVault"]);
+
+        // Each upgrade deploys from its own deployer account at nonce 0
+        let deployed_by = |last_byte| {
+            let mut deployer = address!("4210000000000000000000000000000000000000");
+            deployer[19] = last_byte;
+            deployer.create(0)
+        };
+        for network in load_registry(DEFAULT_NETWORKS).unwrap() {
+            for (name, deployers) in [
+                // Ecotone, Fjord and Isthmus
+                ("GasPriceOracle", &[1, 2, 4][..]),
+                // Ecotone and Isthmus
+                ("L1Block", &[0, 3]),
+                // Isthmus
+                ("OperatorFeeVault", &[5]),
+            ] {
+                let predeploy = PREDEPLOYS.iter().find(|p| p.name == name).unwrap();
+                let expected: Vec<_> = std::iter::once(predeploy.implementation())
+                    .chain(deployers.iter().map(|d| deployed_by(*d)))
+                    .collect();
+                assert_eq!(
+                    predeploy.accepted_implementations(&network),
+                    expected,
+                    "{} on {}",
+                    name,
+                    network.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn implementation_keeps_the_last_two_bytes_of_the_predeploy() {
+        let named = |name| PREDEPLOYS.iter().find(|p| p.name == name).unwrap();
+        assert_eq!(
+            named("L2CrossDomainMessenger").implementation(),
+            address!("c0d3c0d3c0d3c0d3c0d3c0d3c0d3c0d3c0d30007")
+        );
+        assert_eq!(
+            named("L1FeeVault").implementation(),
+            address!("c0d3c0d3c0d3c0d3c0d3c0d3c0d3c0d3c0d3001a")
+        );
+    }
+
+    #[test]
+    fn only_0x4200_predeploys_other_than_weth_and_the_token_are_proxied() {
+        for predeploy in PREDEPLOYS {
+            let expected = predeploy.address[..18]
+                == address!("4200000000000000000000000000000000000000")[..18]
+                && !["WETH9", "GovernanceToken"].contains(&predeploy.name);
+            assert_eq!(predeploy.proxied, expected, "{}", predeploy.name);
+        }
+    }
+}
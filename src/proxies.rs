@@ -0,0 +1,301 @@
+use alloy::eips::BlockId;
+use alloy::primitives::{Address, B256};
+use alloy::providers::{Provider, ReqwestProvider};
+use alloy::sol_types::SolCall;
+use anyhow::Result;
+use std::str::FromStr;
+
+use crate::CheckResult;
+use crate::abi::{Multicall3, ProxyAdmin};
+use crate::constants::{
+    EIP1967_ADMIN_SLOT, EIP1967_IMPLEMENTATION_SLOT, IMPLEMENTATION_COLUMN, NON_EIP1967_PROXY_ROWS,
+    PROXY_ADMIN_ROW, PROXY_ROW_SUFFIX, PROXY_ROWS,
+};
+use crate::parser::{Contract, Network, NetworkRef};
+use crate::value::Value;
+
+/// Row name without qualifiers such as the "(FDG)" of "DelayedWETHProxy (FDG)".
+fn base_name(name: &str) -> &str {
+    name.split(" (").next().unwrap_or(name).trim()
+}
+
+/// Whether a docs row is a proxy managed by the ProxyAdmin, judging by its name.
+pub(crate) fn is_proxy_row(name: &str) -> bool {
+    let base = base_name(name);
+    base.ends_with(PROXY_ROW_SUFFIX) || PROXY_ROWS.iter().any(|row| row.eq_ignore_ascii_case(base))
+}
+
+/// Whether a docs row is a proxy keeping its admin and implementation in EIP-1967 slots.
+fn has_eip1967_slots(name: &str) -> bool {
+    is_proxy_row(name)
+        && !NON_EIP1967_PROXY_ROWS
+            .iter()
+            .any(|row| row.eq_ignore_ascii_case(base_name(name)))
+}
+
+/// Implementation address listed for a row in the "Implementation" column, if any.
+pub(crate) fn documented_implementation(contract: &Contract) -> Option<Result<Address, String>> {
+    let cell = contract.column(IMPLEMENTATION_COLUMN)?;
+    Some(
+        Address::from_str(cell.text.trim())
+            .map_err(|_| format!("Unrecognized {} '{}'", IMPLEMENTATION_COLUMN, cell.text)),
+    )
+}
+
+fn proxy_rows<'a>(networks: &'a [Network], location: NetworkRef<'_>) -> Vec<&'a Contract> {
+    networks
+        .iter()
+        .filter(|n| n.location() == location)
+        .flat_map(|n| &n.contracts)
+        .collect()
+}
+
+/// ProxyAdmin functions each proxy row is verified with.
+const FUNCTIONS: [&str; 2] = ["getProxyAdmin", "getProxyImplementation"];
+
+fn find_proxy_admin(contracts: &[&Contract]) -> Option<Address> {
+    contracts
+        .iter()
+        .find(|c| c.name.eq_ignore_ascii_case(PROXY_ADMIN_ROW))
+        .and_then(|c| Address::from_str(&c.address).ok())
+}
+
+/// Reads the EIP-1967 admin and implementation slots of every proxy row of `location`.
+///
+/// The admin must be the documented `ProxyAdmin`. The implementation is only compared
+/// when the docs list it, in an "Implementation" column. A slot that cannot be read fails
+/// its own result only.
+pub async fn verify_proxy_slots(
+    provider: &ReqwestProvider,
+    block: BlockId,
+    networks: &[Network],
+    location: NetworkRef<'_>,
+) -> Vec<CheckResult> {
+    let contracts = proxy_rows(networks, location);
+    let proxy_admin = find_proxy_admin(&contracts);
+
+    let mut results = Vec::new();
+
+    for contract in contracts.iter().filter(|c| has_eip1967_slots(&c.name)) {
+        let mut admin = CheckResult::new(format!("{} (EIP-1967 admin)", contract.name), location);
+        let address = match Address::from_str(&contract.address) {
+            Ok(address) => address,
+            Err(e) => {
+                admin.error = Some(format!("Error parsing address: {}", e));
+                results.push(admin);
+                continue;
+            }
+        };
+        let read_slot = |slot: B256| async move {
+            let word = provider
+                .get_storage_at(address, slot.into())
+                .block_id(block)
+                .await
+                .map_err(|e| format!("Failed to read storage: {}", e))?;
+            Ok::<_, String>(Address::from_word(word.into()))
+        };
+
+        match proxy_admin {
+            None => admin.missing_dependency = Some(PROXY_ADMIN_ROW.to_string()),
+            Some(expected) => {
+                admin.expected = Some(Value::Address(expected));
+                match read_slot(EIP1967_ADMIN_SLOT).await {
+                    Err(e) => admin.error = Some(e),
+                    Ok(actual) => {
+                        admin.actual = Some(Value::Address(actual));
+                        admin.success = actual == expected;
+                    }
+                }
+            }
+        }
+        results.push(admin);
+
+        let Some(documented) = documented_implementation(contract) else {
+            continue;
+        };
+        let mut implementation = CheckResult::new(
+            format!("{} (EIP-1967 implementation)", contract.name),
+            location,
+        );
+        match documented {
+            Err(e) => implementation.error = Some(e),
+            Ok(expected) => {
+                implementation.expected = Some(Value::Address(expected));
+                match read_slot(EIP1967_IMPLEMENTATION_SLOT).await {
+                    Err(e) => implementation.error = Some(e),
+                    Ok(actual) => {
+                        implementation.actual = Some(Value::Address(actual));
+                        implementation.success = actual == expected;
+                    }
+                }
+            }
+        }
+        results.push(implementation);
+    }
+
+    results
+}
+
+/// Asks the documented `ProxyAdmin` for the admin and implementation of every proxy row of
+/// `location`, which also covers the legacy ChugSplash and ResolvedDelegate proxies.
+///
+/// The reported admin must be the ProxyAdmin itself. For a proxy managed by a different
+/// admin, the call reverts or reports that admin. The implementation is compared against
+/// the "Implementation" column when the docs list it, and must be set otherwise. If the
+/// multicall fails, every proxy row reports the error.
+pub async fn verify_proxy_admin(
+    provider: &ReqwestProvider,
+    multicall3: Address,
+    block: BlockId,
+    networks: &[Network],
+    location: NetworkRef<'_>,
+) -> Vec<CheckResult> {
+    let contracts = proxy_rows(networks, location);
+    let new_result = |contract: &Contract, function: &str| {
+        CheckResult::new(
+            format!("{} (ProxyAdmin.{})", contract.name, function),
+            location,
+        )
+    };
+
+    let mut results = Vec::new();
+    let mut proxies = Vec::new();
+    for contract in contracts.iter().filter(|c| is_proxy_row(&c.name)) {
+        match Address::from_str(&contract.address) {
+            Ok(address) => proxies.push((*contract, address)),
+            Err(e) => results.extend(FUNCTIONS.map(|function| CheckResult {
+                error: Some(format!("Error parsing address: {}", e)),
+                ..new_result(contract, function)
+            })),
+        }
+    }
+
+    let Some(proxy_admin) = find_proxy_admin(&contracts) else {
+        results.extend(proxies.iter().flat_map(|(contract, _)| {
+            FUNCTIONS.map(|function| CheckResult {
+                missing_dependency: Some(PROXY_ADMIN_ROW.to_string()),
+                ..new_result(contract, function)
+            })
+        }));
+        return results;
+    };
+    if proxies.is_empty() {
+        return results;
+    }
+
+    let mut calls = Vec::with_capacity(proxies.len() * 2);
+    for (_, proxy) in &proxies {
+        calls.push(Multicall3::Call3 {
+            target: proxy_admin,
+            allowFailure: true,
+            callData: ProxyAdmin::getProxyAdminCall { proxy: *proxy }
+                .abi_encode()
+                .into(),
+        });
+        calls.push(Multicall3::Call3 {
+            target: proxy_admin,
+            allowFailure: true,
+            callData: ProxyAdmin::getProxyImplementationCall { proxy: *proxy }
+                .abi_encode()
+                .into(),
+        });
+    }
+
+    let multicall = Multicall3::new(multicall3, provider.clone());
+    let returns = match multicall.aggregate3(calls).block(block).call().await {
+        Ok(returns) => returns.returnData,
+        Err(e) => {
+            results.extend(proxies.iter().flat_map(|(contract, _)| {
+                FUNCTIONS.map(|function| CheckResult {
+                    error: Some(format!("ProxyAdmin multicall failed: {}", e)),
+                    ..new_result(contract, function)
+                })
+            }));
+            return results;
+        }
+    };
+
+    for (i, (contract, _)) in proxies.iter().enumerate() {
+        let mut admin = new_result(contract, "getProxyAdmin");
+        admin.expected = Some(Value::Address(proxy_admin));
+        let res = &returns[i * 2];
+        match ProxyAdmin::getProxyAdminCall::abi_decode_returns(&res.returnData, true) {
+            Ok(r) if res.success => {
+                admin.actual = Some(Value::Address(r._0));
+                admin.success = r._0 == proxy_admin;
+            }
+            _ => {
+                admin.error = Some(
+                    "getProxyAdmin reverted; the proxy is likely managed by a different admin"
+                        .to_string(),
+                );
+            }
+        }
+        results.push(admin);
+
+        let mut implementation = new_result(contract, "getProxyImplementation");
+        let res = &returns[i * 2 + 1];
+        let actual =
+            match ProxyAdmin::getProxyImplementationCall::abi_decode_returns(&res.returnData, true)
+            {
+                Ok(r) if res.success => r._0,
+                _ => {
+                    implementation.error = Some("getProxyImplementation reverted".to_string());
+                    results.push(implementation);
+                    continue;
+                }
+            };
+        implementation.actual = Some(Value::Address(actual));
+        match documented_implementation(contract) {
+            Some(Err(e)) => implementation.error = Some(e),
+            Some(Ok(expected)) => {
+                implementation.expected = Some(Value::Address(expected));
+                implementation.success = actual == expected;
+            }
+            None if actual == Address::ZERO => {
+                implementation.error = Some("ProxyAdmin reports no implementation".to_string());
+            }
+            None => implementation.success = true,
+        }
+        results.push(implementation);
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_proxy_rows_by_name() {
+        // (row, base name, managed by the ProxyAdmin, has EIP-1967 slots)
+        for (name, base, proxy, eip1967) in [
+            (
+                "DisputeGameFactoryProxy",
+                "DisputeGameFactoryProxy",
+                true,
+                true,
+            ),
+            ("DelayedWETHProxy (FDG)", "DelayedWETHProxy", true, true),
+            ("DelayedWETHProxy (PDG)", "DelayedWETHProxy", true, true),
+            ("OptimismPortal", "OptimismPortal", true, true),
+            ("systemconfig", "systemconfig", true, true),
+            ("L1StandardBridge", "L1StandardBridge", true, true),
+            (
+                "L1CrossDomainMessenger",
+                "L1CrossDomainMessenger",
+                true,
+                false,
+            ),
+            ("MIPS", "MIPS", false, false),
+            ("FaultDisputeGame", "FaultDisputeGame", false, false),
+            ("ProxyAdmin", "ProxyAdmin", false, false),
+            ("AddressManager", "AddressManager", false, false),
+        ] {
+            assert_eq!(base_name(name), base, "{}", name);
+            assert_eq!(is_proxy_row(name), proxy, "{}", name);
+            assert_eq!(has_eip1967_slots(name), eip1967, "{}", name);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use alloy::primitives::Address;
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::constants::MULTICALL3_ADDRESS;
+
+/// Network registry shipped with the binary, used when no `--networks` file is given.
+pub const DEFAULT_NETWORKS: &str = include_str!("../networks/default.toml");
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RegistryFile {
+    #[serde(rename = "network", default)]
+    networks: Vec<NetworkConfig>,
+}
+
+/// An L1 and the L2 settling on it, as documented and verified together.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkConfig {
+    /// Short name used on the command line, e.g. `mainnet`
+    pub name: String,
+    pub l1_header: String,
+    pub l2_header: String,
+    pub rpc_env: String,
+    pub chain_id: u64,
+    #[serde(default = "default_multicall3")]
+    pub multicall3: Address,
+    pub l2_rpc_env: String,
+    pub l2_chain_id: u64,
+    #[serde(default = "default_multicall3")]
+    pub l2_multicall3: Address,
+    /// Predeploy names the docs list at another deployment on purpose, with that address
+    #[serde(default)]
+    pub non_canonical_predeploys: BTreeMap<String, Address>,
+    /// Predeploy names mapped to the known implementations outside the 0xc0d3… namespace
+    /// they may use, e.g. those deployed by hard fork upgrades
+    #[serde(default)]
+    pub predeploy_implementations: BTreeMap<String, Vec<Address>>,
+    pub l1_explorer: String,
+    pub l2_explorer: String,
+}
+
+/// One of the two chains of a registry entry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Layer {
+    #[default]
+    L1,
+    L2,
+}
+
+impl Layer {
+    /// Command-line flag giving the RPC URL of this layer.
+    pub fn rpc_flag(self) -> &'static str {
+        match self {
+            Layer::L1 => "--rpc-url",
+            Layer::L2 => "--l2-rpc-url",
+        }
+    }
+}
+
+impl NetworkConfig {
+    /// Header the chain is documented under.
+    pub fn header(&self, layer: Layer) -> &str {
+        match layer {
+            Layer::L1 => &self.l1_header,
+            Layer::L2 => &self.l2_header,
+        }
+    }
+
+    pub fn rpc_env(&self, layer: Layer) -> &str {
+        match layer {
+            Layer::L1 => &self.rpc_env,
+            Layer::L2 => &self.l2_rpc_env,
+        }
+    }
+
+    pub fn chain_id(&self, layer: Layer) -> u64 {
+        match layer {
+            Layer::L1 => self.chain_id,
+            Layer::L2 => self.l2_chain_id,
+        }
+    }
+
+    pub fn multicall3(&self, layer: Layer) -> Address {
+        match layer {
+            Layer::L1 => self.multicall3,
+            Layer::L2 => self.l2_multicall3,
+        }
+    }
+}
+
+fn default_multicall3() -> Address {
+    Address::from_str(MULTICALL3_ADDRESS).expect("Invalid Multicall3 constant")
+}
+
+pub fn load_registry(source: &str) -> Result<Vec<NetworkConfig>> {
+    let file: RegistryFile = toml::from_str(source).context("Invalid network registry")?;
+
+    for (i, network) in file.networks.iter().enumerate() {
+        if file.networks[..i].iter().any(|n| n.name == network.name) {
+            return Err(anyhow!("Duplicate network name '{}'", network.name));
+        }
+    }
+
+    Ok(file.networks)
+}
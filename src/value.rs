@@ -0,0 +1,33 @@
+use alloy::primitives::{Address, B256, U256};
+use std::fmt;
+
+/// A typed value read on-chain or expected from the docs/config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Address(Address),
+    Bytes32(B256),
+    Uint(U256),
+    String(String),
+}
+
+impl Value {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Value::Address(_) => "address",
+            Value::Bytes32(_) => "bytes32",
+            Value::Uint(_) => "uint",
+            Value::String(_) => "string",
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Address(a) => write!(f, "{}", a),
+            Value::Bytes32(b) => write!(f, "{}", b),
+            Value::Uint(u) => write!(f, "{}", u),
+            Value::String(s) => write!(f, "{:?}", s),
+        }
+    }
+}